@@ -1,5 +1,5 @@
 #!/bin/env python
-from pygs import ufo_model, GoSamProcess, AmplitudeType
+from pygs import ufo_model, GoSamProcess, AmplitudeType, Scale, Frame
 from logging import info, basicConfig
 from rich.logging import RichHandler
 from rich.console import Console
@@ -54,3 +54,8 @@ if __name__ == "__main__":
     print(f"NLO squared: {res[2]}")
     print(f"Single Pole: {res[1]}")
     print(f"Double Pole: {res[0]}")
+
+    # The amplitude is Lorentz invariant, the same point evaluated in different frames yields the same result
+    for frame in [None, Frame.Lab(2000., 125.), Frame.RandomAzimuth(), Frame.Rest(2)]:
+        vecs, res, _ = proc.eval_random(0, Scale.Fixed(1E6), scale = SCALE, seed = 1, frame = frame)
+        print(f"{str(frame):<28} p1 = {vecs[0][0]:.1f} GeV, LO squared: {res[3]}")
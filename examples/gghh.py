@@ -57,6 +57,6 @@ if __name__ == "__main__":
         masses.append(MT)
     with open("gghh_mass.npy", "wb") as f:
         np.save(f, np.array(
-            [[*np.array(l[0]).flatten().tolist(), m, l[1][2]] for m, l in zip(masses, sp_0_res)]
+            [[*np.array(l[0]).flatten().tolist(), m, l[1].finite] for m, l in zip(masses, sp_0_res)]
         ))
     info(f"Saved {N_POINTS} point from subprocess 0 to file 'gghh_mass.npy'")
@@ -59,6 +59,6 @@ if __name__ == "__main__":
         #masses.append(MT)
     with open("gghh_flux.npy", "wb") as f:
         np.save(f, np.array(
-            [[*np.array(l[0]).flatten().tolist(), l[1][2]] for l in sp_0_res]
+            [[*np.array(l[0]).flatten().tolist(), l[1].finite] for l in sp_0_res]
         ))
     info(f"Saved {N_POINTS} point from subprocess 0 to file 'gghh_flux.npy'")
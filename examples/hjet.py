@@ -1,5 +1,5 @@
 #!/bin/env python
-from pygs import ufo_model, GoSamProcess, AmplitudeType, Scale
+from pygs import ufo_model, GoSamProcess, AmplitudeType, Scale, Cut
 from logging import info, basicConfig
 from rich.logging import RichHandler
 from rich.console import Console
@@ -42,11 +42,14 @@ if __name__ == "__main__":
         proc.setup()
     info("Successfully setup the process library")
 
+    # Avoid the soft and collinear region of the final-state gluon
+    proc.cuts = [Cut.PT([21], min = 20.0)]
+
     info(f"Sampling {N_POINTS} points from subprocess 0: 'g g -> H g'")
     s = Scale.Uniform(MH**2, 1E4**2)
-    sp_0_res = proc.sample(0, s, N_POINTS, scale = SCALE)
+    momenta, results, *_ = proc.sample_array(0, s, N_POINTS, scale = SCALE)
+    n_accepted = len(momenta)
+    info(f"{n_accepted} points passed the cuts (efficiency {proc.cut_efficiency:.3f})")
     with open("hjet.npy", "wb") as f:
-        np.save(f, np.array(
-            [np.append(np.array(l[0]).flatten(), np.array(l[1][2])) for l in sp_0_res]
-        ))
-    info(f"Saved {N_POINTS} point from subprocess 0 to file 'hjet.npy'")
+        np.save(f, np.column_stack((momenta.reshape(n_accepted, -1), results[:, 2])))
+    info(f"Saved {n_accepted} point from subprocess 0 to file 'hjet.npy'")
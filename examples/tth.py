@@ -82,21 +82,21 @@ if __name__ == "__main__":
     info(f"Sampling {N_POINTS} points from subprocess 0: 'd dbar -> h t tbar'")
     sp_0_res = proc.sample(0, SCALE, N_POINTS)
     sp_0_sub = [
-        proc.eval(2, SCALE, vecs)[9] / beta_tt(np.array(vecs)) for vecs, _ in sp_0_res
+        proc.eval(2, SCALE, vecs)[3, 4] / beta_tt(np.array(vecs)) for vecs, *_ in sp_0_res
     ]
     with open("subprocess_0.npy", "wb") as f:
         np.save(f, np.array(
-            [np.append(np.array(l[0]).flatten(), [*np.array(l[1][2:]), sp_0_sub[i]]) for i, l in enumerate(sp_0_res)]
+            [np.append(np.array(l[0]).flatten(), [*np.array(l[1].values[2:]), sp_0_sub[i]]) for i, l in enumerate(sp_0_res)]
         ))
     info(f"Saved {N_POINTS} point from subprocess 0 to file 'subprocess_0.npy'")
 
     info(f"Sampling {N_POINTS} points from subprocess 1: 'g g -> h t tbar'")
     sp_1_res = proc.sample(0, SCALE, N_POINTS)
     sp_1_sub = [
-        proc.eval(3, SCALE, vecs)[9] / beta_tt(np.array(vecs)) for vecs, _ in sp_1_res
+        proc.eval(3, SCALE, vecs)[3, 4] / beta_tt(np.array(vecs)) for vecs, *_ in sp_1_res
     ]
     with open("subprocess_1.npy", "wb") as f:
         np.save(f, np.array(
-            [np.append(np.array(l[0]).flatten(), [*np.array(l[1][2:]), sp_1_sub[i]]) for i, l in enumerate(sp_1_res)]
+            [np.append(np.array(l[0]).flatten(), [*np.array(l[1].values[2:]), sp_1_sub[i]]) for i, l in enumerate(sp_1_res)]
         ))
     info(f"Saved {N_POINTS} point from subprocess 1 to file 'subprocess_1.npy'")
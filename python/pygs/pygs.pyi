@@ -4,6 +4,9 @@ from enum import Enum
 from math import atan
 from typing import Any, Optional
 
+import numpy as np
+import numpy.typing as npt
+
 def ufo_model(path: str) -> Model:
     """Import the UFO model at the given localtion"""
 
@@ -13,39 +16,85 @@ class Model:
     def __new__(cls, path: str) -> Model:
         """Create an empty model"""
 
-    def add_particle(self, id: int, name: str, mass: float):
-        """Add a particle with the given parameters to the model"""
+    def add_particle(
+        self, id: int, name: str, mass: float, mass_ident: str, spin: int = 1, color: int = 1
+    ):
+        """
+        Add a particle with the given parameters to the model. `spin` and `color` follow the UFO conventions, i.e.
+        `spin = 2s + 1` and `color` is the dimension of the colour representation.
+        """
 
-class GoSamProcess:
-    """A process backed by GoSam"""
+class Process:
+    """
+    A process evaluated by a one-loop provider, the common base of [GoSamProcess] and [BLHAProcess] providing the
+    evaluation and sampling methods. Methods taking a subprocess `id` raise `IndexError` if there is no such
+    subprocess and `ValueError` if it was added after [GoSamProcess.setup].
+    """
 
-    def __new__(
-        cls,
-        coupling_orders: dict[str, int],
-        model: Model,
-        nlo_coupling: Optional[str] = None,
-        contract_options: Optional[dict[str, str]] = None,
-        gosam_options: Optional[dict[str, str]] = None,
-    ) -> GoSamProcess:
-        "Create a new GoSam process"
+    seed: int
+    """Seed of the process"""
+    last_seed: int
+    """Seed used by the last call to [eval_random], [sample], [sample_array] or [sample_points]"""
+    cuts: list[Cut]
+    """
+    Generation-level cuts applied to all sampled points. Points failing a cut are rejected before calling the OLP.
+    [sample] and [sample_array] only return the accepted points, with the weights multiplied by the cut efficiency,
+    such that the mean over the returned points still estimates the integral over the full phase space. [integrate],
+    [train_vegas] and [unweighted_events] count rejected points with weight zero, and [eval_random] draws points until
+    one passes the cuts.
+    """
+    cut_efficiency: float
+    """Fraction of the points of the last call to [sample], [sample_array], [integrate] or [unweighted_events] that passed the cuts, including discarded unstable points"""
+    rescue: list[Rescue]
+    """
+    Rescue steps applied in order to points with an accuracy worse than the accuracy threshold, until a step yields
+    a stable result. Such points have the status `Status.Rescued`. If no step succeeds, the most accurate result is
+    kept with the status `Status.Unstable`.
 
-    def add_subprocess(
-        self, incoming: list[int], outgoing: list[int], amplitude_type: AmplitudeType
-    ):
-        """Add a new subprocess"""
+    Example:
+        ```python
+        proc.rescue = [Rescue.Rotate(), Rescue.Parameters({"PSP_rescue": 1}, {"PSP_rescue": 0})]
+        ```
+    """
+    discard_unstable: bool
+    """
+    Whether sampling calls discard points which are still unstable after all rescue steps. Discarded points are not
+    returned and are excluded from the estimates of [integrate] and [unweighted_events], unlike points failing the
+    cuts, which count with weight zero (default: `False`)
+    """
+    n_discarded: int
+    """Number of unstable points discarded by the last sampling call, see [discard_unstable]"""
+    rescue_statistics: dict[str, int]
+    """Number of `"stable"`, `"rescued"` and `"unstable"` points of the last sampling call that passed the cuts"""
+    precision: Precision
+    """Arithmetic precision in which sampling calls generate the phase-space points (default: `Precision.Double`)"""
+    point_tolerance: Optional[float]
+    """
+    Tolerance of the relative violations of momentum conservation and of the mass shells (see [momentum_violation])
+    of points passed to [eval], [eval_batch] and [check_poles]. Points outside the tolerance raise a `ValueError`,
+    `None` evaluates all points without checks (default: `1e-6`). With [project_points], the tolerance is checked
+    after the projection, such that only points that cannot be projected are rejected.
+    """
+    project_points: bool
+    """
+    Whether points passed to [eval], [eval_batch] and [check_poles] are projected onto the mass shells of the model
+    and exact momentum conservation before the evaluation. The incoming momenta are put on-shell and the outgoing
+    momenta are balanced and rescaled in the center of mass frame of the incoming momenta (default: `False`).
+    """
 
-    def setup(self):
-        """
-        Generate, compile and load the process library. The library will be reused for subsequent runs if the
-        configuration is the same.
+    def set_parameter(self, parameter: str, real: float, imag: float):
         """
+        Set parameter `parameter` to value `real + i float`
 
-    def set_parameter(self, parameter: str, real: float, imag: float):
-        """Set parameter `parameter` to value `real + i float`"""
+        Raises:
+            OSError: if the OLP does not provide `OLP_SetParameter`, which is optional for BLHA1 OLPs. The strong
+                coupling `alphas` is always accepted, since BLHA1 passes it with every evaluation.
+        """
 
-    def eval(self, id: int, scale: float, vecs: list[list[float]]) -> list[float]:
+    def eval(self, id: int, scale: float, vecs: list[list[float]]) -> EvalResult:
         """
-        Evaluate subprocess `id` with energy scale `scale` at phase space point `vecs`
+        Evaluate subprocess `id` with energy scale `scale` at phase space point `vecs`, summing the results of its
+        channels (see [eval_channels])
 
         Parameters:
             id: identifier of the subprocess
@@ -53,17 +102,133 @@ class GoSamProcess:
             vecs: phase space point as list of four-vectors, where each four-vector is a list of exactly four floats
 
         Returns:
-            list of floats with length depending on the amplitude type, see BLHA2 standard (1308.3462) for details
+            result depending on the amplitude type, see BLHA2 standard (1308.3462) for details, together with the
+            accuracy reported by the OLP. BLHA1 OLPs report no accuracy, their results have accuracy NaN.
+
+        Raises:
+            ValueError: if the point violates momentum conservation or the mass shells beyond [point_tolerance], or
+                if a BLHA1 OLP is used before `alphas` was set with [set_parameter]
+        """
+
+    def eval_channels(self, id: int, scale: float, vecs: list[list[float]]) -> list[EvalResult]:
+        """
+        Same as [eval], but evaluate each channel of subprocess `id` separately. The OLP can answer a subprocess of
+        the order file with several ids, e.g. for different helicity or colour configurations, whose results [eval]
+        returns summed with the worst accuracy and status of the channels.
+
+        Returns:
+            result of each channel in the order of [channels]
+        """
+
+    def channels(self, id: int) -> list[int]:
+        """OLP ids of the channels of subprocess `id` assigned in the contract"""
+
+    def coupling_powers(self, id: int) -> dict[str, int]:
+        """
+        Coupling powers in effect when subprocess `id` was declared in the contract, e.g. `{"QCD": 3, "QED": 0}` for a
+        real-emission subprocess in a contract which also contains the Born subprocesses
+        """
+
+    def subprocess_options(self, id: int) -> dict[str, str]:
+        """
+        Options other than `AmplitudeType` and `CouplingPower` in effect when subprocess `id` was declared in the
+        contract
         """
 
     def eval_random(
-        self, id: int, s: Scale, scale: Optional[float] = None
-    ) -> tuple[list[list[float]], list[float]]:
+        self,
+        id: int,
+        s: Scale,
+        scale: Optional[float] = None,
+        seed: Optional[int] = None,
+        frame: Optional[Frame] = None,
+    ) -> tuple[list[list[float]], EvalResult, float]:
         """Same as [sample], but only sample one point."""
 
+    def validate(
+        self,
+        id: int,
+        s: Scale,
+        n_points: int = 10,
+        scale: Optional[float] = None,
+        alpha_s: Optional[float] = None,
+        seed: Optional[int] = None,
+    ) -> ValidationResult:
+        """
+        Check the numerical health of the process library for subprocess `id`. Each of the `n_points` random points
+        is re-evaluated after a random rotation and a random boost, and the results have to agree. If the process
+        contains a subprocess with the same amplitude type and exchanged incoming particles, e.g. `d~ u -> W+` for
+        `u d~ -> W+`, or the subprocess has identical incoming particles, it is evaluated at the point with exchanged
+        incoming momenta and has to agree as well. This crossing check applies to `Tree`, `Loop` and `LoopInduced`
+        amplitudes. For `Loop`
+        amplitudes with QCD corrections and massless coloured particles, the double pole is compared to
+        `-alpha_s / (2 pi) sum_i C_i * born` and the single pole to the prediction of the Catani-Seymour I-operator,
+        see [check_poles]. For
+        `LoopInduced` amplitudes the poles have to vanish relative to the finite part. Spin-correlated amplitudes
+        can not be validated.
+
+        Parameters:
+            id: identifier of the subprocess
+            s: center of mass energy squared of the sampled points
+            n_points: number of points to check
+            scale: Renormalization scale to evaluate the amplitude at (default: center of mass energy)
+            alpha_s: strong coupling used by the OLP (default: the value set with `set_parameter("alphas", ...)`, the
+                pole checks are skipped if neither is available)
+            seed: seed of the sampled points, see [sample]
+
+        Returns:
+            the smallest number of digits of agreement of each check over all points
+
+        Example:
+            ```python
+            proc.setup()
+            check = proc.validate(0, Scale.Fixed(1E6), alpha_s=0.118)
+            assert check.digits > 8, check
+            ```
+        """
+
+    def check_poles(
+        self, id: int, scale: float, vecs: list[list[float]], alpha_s: Optional[float] = None
+    ) -> PoleCheck:
+        """
+        Compare the poles of the `Loop` subprocess `id` at the phase-space point `vecs` with the prediction of the
+        Catani-Seymour I-operator for QCD corrections with massless coloured particles,
+
+            double pole = -alpha_s / (2 pi) sum_i C_i * born
+            single pole = alpha_s / (2 pi) (-sum_i gamma_i * born + sum_{i != j} <T_i T_j> ln(scale^2 / |s_ij|))
+
+        in the normalization `(4 pi)^eps / Gamma(1 - eps)`. The colour correlators `<T_i T_j>` are taken from a
+        `ccTree` subprocess with the same particles, which has to be added before [GoSamProcess.setup]. Without such a
+        subprocess, the colour correlators are fixed by colour conservation, which is only possible for up to three
+        coloured particles.
+
+        Parameters:
+            id: identifier of the subprocess
+            scale: Renormalization scale to evaluate the amplitude at
+            vecs: momenta of the phase-space point
+            alpha_s: strong coupling used by the OLP (default: the value set with `set_parameter("alphas", ...)`)
+
+        Example:
+            ```python
+            proc.add_subprocess([21, 21], [21, 21], AmplitudeType.Loop)
+            proc.add_subprocess([21, 21], [21, 21], AmplitudeType.ccTree)
+            proc.setup()
+            vecs, _, _ = proc.eval_random(0, Scale.Fixed(1E6))
+            check = proc.check_poles(0, 1E3, vecs, alpha_s=0.118)
+            print(check.double_pole_digits, check.single_pole_digits)
+            ```
+        """
+
     def sample(
-        self, id: int, s: Scale, n_points: int, scale: Optional[float] = None
-    ) -> list[tuple[list[list[float]], list[float]]]:
+        self,
+        id: int,
+        s: Scale,
+        n_points: int,
+        scale: Optional[float] = None,
+        seed: Optional[int] = None,
+        n_threads: Optional[int] = None,
+        frame: Optional[Frame] = None,
+    ) -> list[tuple[list[list[float]], EvalResult, float]]:
         """
         Evaluate subprocess `id` with energy scale `scale` at `n_points` random phase-space points (constructed by a
         RAMBO generator).
@@ -73,10 +238,566 @@ class GoSamProcess:
             s: center of mass energy squared of the sampled points
             n_points: number of points to sample
             scale: Renormalization scale to evaluate the amplitude at (default: center of mass energy)
+            seed: seed of the sampled points. The same seed always yields the same phase-space points, independent of
+                `n_threads` (default: next seed derived from the process seed)
+            n_threads: number of threads to distribute the points over. Each thread evaluates the points with an
+                independent copy of the process library, which a [BLHAProcess] only allows with `isolated_copies`
+                (default: 1)
+            frame: frame the sampled points are transformed into before the cuts are applied and the amplitude is
+                evaluated (default: center of mass frame, or the lab frame for `Scale.Hadronic`)
 
         Returns:
-            list of sampled points, where each entry contains the phase space point and the result. See also: [eval]
+            list of sampled points, where each entry contains the phase space point, the result and the phase-space
+            weight of the point. The weight is normalized to the n-body phase space measure
+            `dPhi_n = prod_i d^3p_i / ((2 pi)^3 2 E_i) (2 pi)^4 delta^4(P - sum_i p_i)` at the sampled center of mass
+            energy. For `1 -> n` and `n -> 1` subprocesses, `s` has to be fixed to the squared mass of the decaying or
+            produced particle, and the weight of a single outgoing particle is `2 pi`, i.e. `dPhi_1` without
+            `delta(s - m^2)`. See also: [eval], [sample_decay]
+        """
+
+    def sample_decay(
+        self,
+        id: int,
+        n_points: int,
+        scale: Optional[float] = None,
+        seed: Optional[int] = None,
+        n_threads: Optional[int] = None,
+    ) -> list[tuple[list[list[float]], EvalResult, float]]:
         """
+        Evaluate the `1 -> n` subprocess `id` at `n_points` random phase-space points in the rest frame of the
+        decaying particle. Equivalent to [sample] with `s = Scale.Fixed(m**2)`, where `m` is the mass of the decaying
+        particle. Combined with `integrate(..., s=m**2)`, which includes the factor `1 / (2 m)`, this yields the
+        partial decay width.
+
+        Parameters:
+            id: identifier of the subprocess
+            n_points: number of points to sample
+            scale: Renormalization scale to evaluate the amplitude at (default: as in [sample])
+            seed: seed of the sampled points, see [sample]
+            n_threads: number of threads to distribute the points over, see [sample]
+        """
+
+    def eval_batch(
+        self, id: int, momenta: npt.NDArray[np.float64], scales: npt.NDArray[np.float64]
+    ) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+        """
+        Evaluate subprocess `id` at a batch of `N` phase-space points
+
+        Parameters:
+            id: identifier of the subprocess
+            momenta: array of shape `(N, n_legs, 4)` containing the phase-space points
+            scales: array of shape `(N,)` containing the energy scale of each point
+
+        Returns:
+            array of shape `(N, n_results)` containing the raw results (see [eval]) and array of shape `(N,)` containing
+            the accuracies reported by the OLP
+
+        Raises:
+            ValueError: if a point violates momentum conservation or the mass shells beyond [point_tolerance], with the
+                row of the point in the message
+        """
+
+    def sample_array(
+        self,
+        id: int,
+        s: Scale,
+        n_points: int,
+        scale: Optional[float] = None,
+        seed: Optional[int] = None,
+        n_threads: Optional[int] = None,
+        frame: Optional[Frame] = None,
+    ) -> tuple[
+        npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64]
+    ]:
+        """
+        Same as [sample], but return contiguous arrays.
+
+        Returns:
+            array of shape `(n_points, n_legs, 4)` containing the phase-space points, array of shape
+            `(n_points, n_results)` containing the raw results, array of shape `(n_points,)` containing the
+            accuracies reported by the OLP and array of shape `(n_points,)` containing the phase-space weights
+        """
+
+    def integrate(
+        self,
+        id: int,
+        s: float | Scale,
+        n_points: int,
+        component: Optional[Component] = None,
+        scale: Optional[float] = None,
+        target_error: Optional[float] = None,
+        symmetry_factor: bool = False,
+        average: bool = False,
+        seed: Optional[int] = None,
+        n_threads: Optional[int] = None,
+    ) -> IntegrationResult:
+        """
+        Integrate the partonic cross section (or the decay width for subprocesses with one incoming particle) of
+        subprocess `id` with RAMBO points at center of mass energy squared `s`. The result is given in units of GeV^-2
+        (GeV for decay widths). With `s = Scale.Hadronic(...)`, the partonic cross section is convoluted with the
+        parton densities of the collider, yielding the hadronic cross section.
+
+        Parameters:
+            id: identifier of the subprocess, which must have amplitude type `Tree`, `Loop` or `LoopInduced`
+            s: center of mass energy squared, or a `Scale` to sample it from. The flux factor is evaluated at the
+               partonic center of mass energy of each point.
+            n_points: maximal number of points to evaluate
+            component: component of the result to integrate (default: `Born` for `Tree`, `Finite` otherwise)
+            scale: Renormalization scale to evaluate the amplitude at (default: center of mass energy)
+            target_error: stop as soon as the relative Monte Carlo error is smaller than `target_error`
+            symmetry_factor: include the symmetry factor of identical final-state particles. Only needed if the OLP
+                does not already include it.
+            average: average over the spins and colours of the initial state. Only needed if the OLP does not already
+                include the average.
+            seed: seed of the sampled points, see [sample]
+            n_threads: number of threads to distribute the points over, see [sample]
+        """
+
+    def train_vegas(
+        self,
+        id: int,
+        s: Scale,
+        n_iterations: int,
+        n_points: int,
+        component: Optional[Component] = None,
+        scale: Optional[float] = None,
+        n_bins: int = 50,
+        alpha: float = 1.5,
+        seed: Optional[int] = None,
+    ) -> list[IntegrationResult]:
+        """
+        Train a VEGAS grid for subprocess `id` on the phase-space weight times `component` of the result. Once a grid
+        is present, [eval_random], [sample], [sample_array] and [integrate] draw the points from the adapted density
+        and include the Jacobian of the grid in the phase-space weight. An existing grid is refined further.
+
+        Parameters:
+            id: identifier of the subprocess
+            s: center of mass energy squared of the sampled points
+            n_iterations: number of training iterations
+            n_points: number of points per iteration
+            component: component of the result to train on (default: `Born` for `Tree`, `Finite` otherwise)
+            scale: Renormalization scale to evaluate the amplitude at (default: center of mass energy)
+            n_bins: number of bins per dimension of a new grid
+            alpha: convergence rate of the grid adaptation
+            seed: seed of the sampled points
+
+        Returns:
+            estimate of the integral of the phase-space weight times `component` for each iteration
+        """
+
+    def save_vegas_grid(self, id: int, path: str):
+        """Save the VEGAS grid of subprocess `id` to `path`"""
+
+    def load_vegas_grid(self, id: int, path: str):
+        """Load the VEGAS grid of subprocess `id` from `path`"""
+
+    def clear_vegas_grid(self, id: int):
+        """Remove the VEGAS grid of subprocess `id`, such that points are sampled with flat RAMBO again"""
+
+    def unweighted_events(
+        self,
+        id: int,
+        s: Scale,
+        n_events: int,
+        component: Optional[Component] = None,
+        scale: Optional[float] = None,
+        safety_factor: float = 1.0,
+        n_warmup: int = 1000,
+        max_points: Optional[int] = None,
+        seed: Optional[int] = None,
+        n_threads: Optional[int] = None,
+    ) -> UnweightedEvents:
+        """
+        Generate unweighted events of subprocess `id`. Each point is weighted by its phase-space weight times
+        `component` of the result and accepted with probability `|w| / (safety_factor * w_max)`, where the maximum
+        weight `w_max` is determined from `n_warmup` points and kept fixed afterwards. Points exceeding the threshold
+        are accepted with event weight `w / (safety_factor * w_max)`, such that all events share the same normalization.
+        Points are evaluated in batches sized by the efficiency so far, to avoid evaluating far more points than needed.
+
+        Parameters:
+            id: identifier of the subprocess
+            s: center of mass energy squared of the sampled points
+            n_events: number of events to generate
+            component: component of the result to unweight (default: `Born` for `Tree`, `Finite` otherwise)
+            scale: Renormalization scale to evaluate the amplitude at (default: center of mass energy)
+            safety_factor: factor multiplying the maximum weight to reduce the number of overweight events
+            n_warmup: number of points used to initialize the maximum weight
+            max_points: maximal number of points to evaluate after the warm-up (default: unlimited)
+            seed: seed of the sampled points, see [sample]
+            n_threads: number of threads to distribute the points over, see [sample]
+        """
+
+    def write_lhe(
+        self,
+        id: int,
+        path: str,
+        momenta: list[list[list[float]]],
+        weights: list[float] | list[list[float]],
+        scales: Optional[float | list[float]] = None,
+        weight_names: Optional[list[str]] = None,
+        cross_section: Optional[float] = None,
+        cross_section_error: Optional[float] = None,
+        unweighted: bool = False,
+        collider: Optional[Collider] = None,
+    ) -> None:
+        """
+        Write events of subprocess `id` to the Les Houches Event file `path` (version 3.0). The PDG ids are taken from
+        the subprocess and the masses from the model. Coloured partons are assigned a leading-colour flow, in which
+        colour lines connect incoming and outgoing quarks of the same kind where possible and all gluons are attached
+        to a single string, or form a closed loop in purely gluonic subprocesses. Only colour singlets, triplets,
+        antitriplets and octets are supported.
+
+        Parameters:
+            id: identifier of the subprocess
+            path: path of the LHE file
+            momenta: momenta of the events, e.g. `UnweightedEvents.momenta`
+            weights: weight of each event, or a list of weights per event. The first weight is the nominal event
+                     weight, all weights are additionally written to the `<rwgt>` block of the event.
+            scales: scale of all events or of each event (default: partonic center of mass energy)
+            weight_names: names of the weights in the `<rwgt>` block (default: `w0`, `w1`, ... for multiple weights)
+            cross_section: cross section in the `<init>` block (default: mean of the nominal weights, required for
+                unweighted events)
+            cross_section_error: error of the cross section (default: Monte Carlo error of the nominal weights, `0`
+                for unweighted events)
+            unweighted: whether the events are unweighted, e.g. from [unweighted_events], which is written as
+                `IDWTUP = 3` instead of `IDWTUP = -4`
+            collider: collider the events were generated for, e.g. with `Scale.Hadronic`, providing the beam ids and
+                energies of the `<init>` block. Beams with a PDF are protons unless their `pdg` is given (default:
+                the incoming partons of the first event).
+
+        Example:
+            ```python
+            events = proc.unweighted_events(0, Scale.Fixed(1E6), 1000)
+            proc.write_lhe(0, "events.lhe", events.momenta, events.weights, unweighted=True,
+                           cross_section=events.integral, cross_section_error=events.integral_error)
+            ```
+
+        Raises:
+            ValueError: if the colour representations of the subprocess admit no leading-colour flow, if unweighted
+                events are written without cross section, or if a collider is given for a decay
+        """
+
+    def mandelstam(self, id: int, momenta: npt.NDArray[np.float64]) -> npt.NDArray[np.float64]:
+        """
+        Invariants `s_ij = (sigma_i p_i + sigma_j p_j)^2` of all pairs of legs of subprocess `id`, where `sigma` is
+        `1` for incoming and `-1` for outgoing legs, such that `s = s_12`, `t = s_13` and `u = s_14` for `2 -> 2`
+        processes. The diagonal contains the squared masses `p_i^2`.
+
+        Parameters:
+            id: identifier of the subprocess
+            momenta: array of shape `(N, n_legs, 4)`, e.g. the momenta returned by [sample_array]
+
+        Returns:
+            array of shape `(N, n_legs, n_legs)`
+        """
+
+    def momentum_violation(
+        self, id: int, momenta: npt.NDArray[np.float64]
+    ) -> tuple[npt.NDArray[np.float64], npt.NDArray[np.float64]]:
+        """
+        Violation of momentum conservation and of the on-shell conditions of each point for subprocess `id`. The
+        violations are computed from the double precision momenta and are therefore limited by their rounding, use
+        [sample_points] for the violations of the generation in [precision].
+
+        Parameters:
+            id: identifier of the subprocess
+            momenta: array of shape `(N, n_legs, 4)`, e.g. the momenta returned by [sample_array]
+
+        Returns:
+            tuple of the largest deviation of a component of `sum_in p_i - sum_out p_i` relative to the incoming energy
+            and the largest deviation `|p_i^2 - m_i^2| / E_i^2` of each point, each of shape `(N,)`
+        """
+
+    def sample_points(
+        self, id: int, s: Scale, n_points: int, seed: Optional[int] = None
+    ) -> tuple[
+        npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64], npt.NDArray[np.float64]
+    ]:
+        """
+        Generate `n_points` phase-space points of subprocess `id` in [precision] without evaluating them, e.g. to
+        compare the stability of points generated with `Precision.Double` and `Precision.DoubleDouble`. The points are
+        generated in the partonic center-of-mass frame and neither [cuts] nor a frame are applied. With the same seed,
+        the points returned by [sample_array] called without a frame are those of the returned points that pass the
+        cuts, with different weights; with a frame, the points do not correspond.
+
+        Parameters:
+            id: identifier of the subprocess
+            s: center of mass energy squared of the sampled points
+            n_points: number of points to sample
+            seed: seed of the sampled points (default: next seed derived from the process seed)
+
+        Returns:
+            array of shape `(n_points, n_legs, 4)` containing the phase-space points, array of shape `(n_points,)`
+            containing the phase-space weights and arrays of shape `(n_points,)` containing the violations of momentum
+            conservation and of the mass shells (see [momentum_violation]), computed in [precision] before the
+            momenta are rounded to double precision
+        """
+
+class GoSamProcess(Process):
+    """A process backed by GoSam"""
+
+    def __new__(
+        cls,
+        coupling_orders: dict[str, int],
+        model: Model,
+        nlo_coupling: Optional[str] = None,
+        contract_options: Optional[dict[str, str]] = None,
+        gosam_options: Optional[dict[str, str]] = None,
+        accuracy_threshold: Optional[float] = None,
+        seed: Optional[int] = None,
+    ) -> GoSamProcess:
+        """
+        Create a new GoSam process
+
+        Parameters:
+            contract_options: options written to the order file. The option `InterfaceVersion` selects the BLHA version
+                of the order file, either `"BLHA2"` (default) or `"BLHA1"`. BLHA1 orders only support loop amplitudes and
+                the couplings `QCD` and `QED`, and the strong coupling has to be set with `set_parameter("alphas", ...)`
+                before evaluating.
+            accuracy_threshold: points with a reported accuracy worse than this threshold are flagged as unstable
+                (default: `AccuracyTarget` of the contract, if present). Not available for BLHA1 orders, whose OLPs
+                report no accuracy.
+            seed: seed of the process, from which all sampling calls without an explicit seed are derived
+                (default: random seed)
+        """
+
+    def add_subprocess(
+        self,
+        incoming: list[int],
+        outgoing: list[int],
+        amplitude_type: AmplitudeType,
+        coupling_powers: Optional[dict[str, int]] = None,
+    ):
+        """
+        Add a new subprocess, whose id is the number of previously added subprocesses
+
+        Parameters:
+            coupling_powers: powers of some of the couplings of `coupling_orders` overriding them for this subprocess.
+                They are written to the order file and the subprocess is only matched to contract entries with these
+                powers.
+
+        Raises:
+            ValueError: if a coupling of `coupling_powers` has no order in the process
+        """
+
+    def setup(self):
+        """
+        Generate, compile and load the process library. The library will be reused for subsequent runs if the
+        configuration is the same. The subprocesses are matched to the ids assigned by the OLP in the contract by
+        their particles and amplitude type, such that the ids of [add_subprocess] remain valid.
+
+        Raises:
+            SyntaxError: if the OLP rejected a subprocess or it is missing in the contract
+        """
+
+class BLHAProcess(Process):
+    """
+    A process bound to an existing order/contract/library triple of any BLHA1 or BLHA2 one-loop provider, e.g.
+    OpenLoops, Recola or MadLoop, without running GoSam. The subprocesses are taken from the contract and numbered in
+    order of their OLP ids, see [subprocesses]. All evaluation and sampling methods are inherited from [Process].
+
+    Example:
+        ```python
+        proc = BLHAProcess("OLE_contract.lh", "libopenloops.so", ufo_model("models/sm"))
+        proc.set_parameter("alphas", 0.118, 0.)
+        res = proc.eval(0, 91.188, vecs)
+        ```
+    """
+
+    def __new__(
+        cls,
+        contract_path: str,
+        library_path: str,
+        model: Model,
+        accuracy_threshold: Optional[float] = None,
+        seed: Optional[int] = None,
+        tree_layout: TreeLayout = TreeLayout.Born,
+        isolated_copies: bool = False,
+    ) -> BLHAProcess:
+        """
+        Load the OLP library `library_path` and start it with the contract `contract_path`
+
+        Parameters:
+            contract_path: contract returned by the OLP for the order file
+            library_path: shared library of the OLP providing the BLHA functions
+            model: model providing the masses, spins and colours of the particles of the contract
+            accuracy_threshold: points with a reported accuracy worse than this threshold are flagged as unstable
+                (default: `AccuracyTarget` of the contract, if present). Not available for BLHA1 contracts, whose OLPs
+                report no accuracy.
+            seed: seed of the process, from which all sampling calls without an explicit seed are derived
+                (default: random seed)
+            tree_layout: layout of the values the OLP returns for `Tree` amplitudes. The results are converted to
+                the layout of `Loop` results in either case.
+            isolated_copies: whether copies of the library share no state, such that sampling methods can evaluate
+                points with `n_threads > 1`. Only the library itself is copied, so libraries loading further
+                libraries with global state at runtime are not isolated (default: `False`).
+
+        Raises:
+            OSError: if the library cannot be loaded or lacks a required BLHA function
+            SyntaxError: if the contract cannot be parsed or the OLP fails to start
+            ValueError: if a particle of the contract is missing in the model
+        """
+
+    subprocesses: list[tuple[list[int], list[int], AmplitudeType, list[int]]]
+    """
+    Subprocesses of the contract as `(incoming, outgoing, amplitude_type, olp_ids)`, indexed by the id used by the
+    evaluation and sampling methods
+    """
+
+class Component(Enum):
+    """
+    Component of a `Tree`, `Loop` or `LoopInduced` result. Possible values:
+
+    - DoublePole
+    - SinglePole
+    - Finite
+    - Born
+    """
+
+    DoublePole: ...
+    SinglePole: ...
+    Finite: ...
+    Born: ...
+
+class IntegrationResult:
+    """Monte Carlo estimate of an integral"""
+
+    value: float
+    """Estimate of the integral"""
+    error: float
+    """Monte Carlo error of the estimate"""
+    relative_error: float
+    """Relative Monte Carlo error of the estimate"""
+    n_points: int
+    """Number of evaluated points"""
+    history: list[tuple[int, float, float]]
+    """Running number of points, estimate and error"""
+
+class UnweightedEvents:
+    """Unweighted events of a subprocess"""
+
+    momenta: list[list[list[float]]]
+    """Phase-space points of the events"""
+    weights: list[float]
+    """Event weights, `±1` for regular events and `w / (safety_factor * w_max)` for overweight events"""
+    max_weight: float
+    """Maximum weight of the warm-up, relative to which the event weights are given"""
+    n_trials: int
+    """Number of points tried after the warm-up"""
+    n_overweight: int
+    """Number of overweight events"""
+    efficiency: float
+    """Unweighting efficiency, i.e. the number of events divided by the number of trials"""
+    integral: float
+    """Mean weight of all trials"""
+    integral_error: float
+    """Monte Carlo error of the mean weight"""
+
+    def __len__(self) -> int: ...
+
+class LoopResult:
+    """Result of a `Tree`, `Loop` or `LoopInduced` amplitude"""
+
+    double_pole: float
+    """Coefficient of the double pole"""
+    single_pole: float
+    """Coefficient of the single pole"""
+    finite: float
+    """Finite part"""
+    born: float
+    """Born-level result"""
+    values: list[float]
+    """All four values in BLHA2 order: `[double_pole, single_pole, finite, born]`"""
+    accuracy: float
+    """Accuracy of the result as reported by the OLP"""
+    status: Status
+    """Stability of the result after the rescue steps of the process, `Status.Unstable` if the accuracy is worse than
+    the accuracy threshold of the process"""
+
+class ColorCorrelatedResult:
+    """Result of a `ccTree` amplitude, indexed by a pair of legs `(i, j)` with `i != j`"""
+
+    values: list[float]
+    """Raw values in BLHA2 order, where the correlator of legs `i < j` is stored at position `i + j * (j - 1) / 2`"""
+    n_legs: int
+    """Number of legs of the subprocess"""
+    accuracy: float
+    """Accuracy of the result as reported by the OLP"""
+    status: Status
+    """Stability of the result after the rescue steps of the process, `Status.Unstable` if the accuracy is worse than
+    the accuracy threshold of the process"""
+
+    def __getitem__(self, index: tuple[int, int]) -> float:
+        """Colour-correlated Born `<M|T_i T_j|M>` of legs `i` and `j`"""
+
+class SpinCorrelatedResult:
+    """Result of a `scTree` or `scTree2` amplitude, indexed by the helicity-flipped leg `i` and the spectator `j`"""
+
+    values: list[float]
+    """Raw values in BLHA2 order, alternating real and imaginary parts"""
+    n_legs: int
+    """Number of legs of the subprocess"""
+    accuracy: float
+    """Accuracy of the result as reported by the OLP"""
+    status: Status
+    """Stability of the result after the rescue steps of the process, `Status.Unstable` if the accuracy is worse than
+    the accuracy threshold of the process"""
+
+    def __getitem__(self, index: tuple[int, int]) -> complex:
+        """Spin correlator `<M_{-,i}|T_i T_j|M_{+,i}>` of legs `i` and `j`"""
+
+    def correlators(self, leg: int, helicity: int) -> list[complex]:
+        """
+        Spin correlators `<M_{-h,i}|T_i T_j|M_{h,i}>` of the gluon `i = leg` with helicity `h = helicity` in the ket,
+        indexed by the spectator `j`. BLHA2 provides the correlators for `h = 1`, those for `h = -1` are their complex
+        conjugates.
+
+        Raises:
+            IndexError: if `leg` is not a leg of the subprocess
+            ValueError: if `helicity` is not `1` or `-1`
+        """
+
+EvalResult = LoopResult | ColorCorrelatedResult | SpinCorrelatedResult
+
+class Status(Enum):
+    """
+    Numerical stability of an evaluated point. Possible values:
+
+    - Stable: accuracy within the threshold, or no threshold or accuracy (NaN) to compare
+    - Rescued: accuracy within the threshold after a rescue step
+    - Unstable: accuracy worse than the threshold after all rescue steps
+    """
+
+    Stable: ...
+    Rescued: ...
+    Unstable: ...
+
+class Precision(Enum):
+    """
+    Arithmetic precision of the phase-space generation. Possible values:
+
+    - Double: generation in double precision
+    - DoubleDouble: generation in double-double arithmetic with about 32 significant digits, such that momentum
+      conservation and on-shellness hold to about 30 digits before the momenta are rounded to double precision for
+      the OLP. The same seed yields the same points as in double precision up to rounding.
+    """
+
+    Double: ...
+    DoubleDouble: ...
+
+class Rescue:
+    """
+    Step of the rescue system of [Process.rescue]. Possible values:
+
+    - Rotate(axis=[1, 1, 1], angle=1): re-evaluate the point rotated by `angle` around `axis`, which changes the
+      numerical path through the reduction while leaving the result unchanged. Not applied to spin-correlated
+      amplitudes.
+    - Parameters(switch, restore): re-evaluate the point after setting the OLP parameters `switch`, e.g. to select
+      a higher precision or an alternative reduction, and set the parameters `restore` afterwards
+    """
+
+    Rotate: ...
+    Parameters: ...
 
 class AmplitudeType(Enum):
     """
@@ -97,6 +818,18 @@ class AmplitudeType(Enum):
     Loop: ...
     LoopInduced: ...
 
+class TreeLayout(Enum):
+    """
+    Layout of the values returned by an OLP for `Tree` amplitudes. Possible values:
+
+    - Born: the Born matrix element as the only value, as specified by BLHA2
+    - Loop: the layout of `Loop` amplitudes with vanishing poles and finite part and the Born matrix element as the
+      fourth value, as returned by GoSam
+    """
+
+    Born: ...
+    Loop: ...
+
 class Scale(Enum):
     """
     Type of energy scale. Possible values:
@@ -104,8 +837,234 @@ class Scale(Enum):
     - Fixed: fixed energy scale
     - Uniform: uniformly sampled energy scale in some range
     - Reciprocal: sampled according to a 1/s PDF in some range
+    - Hadronic: partonic center of mass energy sampled from the momentum fractions of the partons in the beams of a
+      `Collider`, with minimal partonic center of mass energy squared `min`. The sampled points are boosted to the
+      lab frame and their weight includes the parton densities, i.e. the weight is normalized to
+      `dx_1 dx_2 f_1(x_1) f_2(x_2) dPhi_n`.
     """
 
     Fixed: ...
     Uniform: ...
     Reciprocal: ...
+    Hadronic: ...
+
+class ValidationResult:
+    """
+    Digits of agreement of the self-checks of [Process.validate], the minimum over all points and values
+    """
+
+    n_points: int
+    """Number of checked points"""
+    rotation: float
+    """Agreement after a random rotation of each point"""
+    boost: float
+    """Agreement after a random boost of each point"""
+    crossing: Optional[float]
+    """
+    Agreement with the subprocess with exchanged incoming particles, evaluated at exchanged incoming momenta, `None`
+    if there is no such subprocess
+    """
+    double_pole: Optional[float]
+    """Agreement of the double pole with the expected infrared structure, `None` if not checked"""
+    single_pole: Optional[float]
+    """Agreement of the single pole with the expected infrared structure, `None` if not checked"""
+    digits: float
+    """Smallest number of digits of all checks"""
+
+class PoleCheck:
+    """Poles of a `Loop` amplitude at a single point together with the prediction of the I-operator"""
+
+    double_pole: float
+    single_pole: float
+    expected_double_pole: float
+    expected_single_pole: float
+    double_pole_digits: float
+    """Digits of agreement of the double pole with the prediction"""
+    single_pole_digits: float
+    """Digits of agreement of the single pole with the prediction"""
+
+class Frame:
+    """
+    Frame of the sampled phase-space points. RAMBO points are generated in the center of mass frame of the incoming
+    particles with the beams along the z-axis. The phase-space weight is Lorentz invariant and not affected by the
+    frame. Possible values:
+
+    - CentreOfMass(): rest frame of the sum of the incoming momenta, e.g. the partonic center of mass frame of points
+      of a `Scale.Hadronic`
+    - Lab(energy1, energy2): lab frame of two beams with energies `energy1` and `energy2` colliding head-on along the
+      z-axis, i.e. the center of mass frame boosted by the rapidity `ln(energy1 / energy2) / 2`. Requires two incoming
+      particles and a fixed scale `s = 4 energy1 energy2`, such that the incoming momenta equal the beam momenta.
+    - RandomAzimuth(): rotation around the beam axis by a uniformly distributed angle
+    - Rest(index): rest frame of the massive leg `index`
+
+    Example:
+        ```python
+        # The same point in two frames, the amplitude is Lorentz invariant
+        _, res_cm, _ = proc.eval_random(0, Scale.Fixed(1E6), seed=1)
+        _, res_lab, _ = proc.eval_random(0, Scale.Fixed(1E6), seed=1, frame=Frame.Lab(1000, 250))
+        ```
+    """
+
+    CentreOfMass: ...
+    Lab: ...
+    RandomAzimuth: ...
+    Rest: ...
+
+class Cut:
+    """
+    Generation-level cut on the final-state particles of a phase-space point, selected by their PDG ids. Possible
+    values:
+
+    - PT(particles, min=0, max=inf): `min <= pT <= max` for all selected particles
+    - Rapidity(particles, max): `|y| <= max` for all selected particles
+    - DeltaR(particles1, particles2, min): `Delta R >= min` for all pairs of distinct particles from the two groups,
+      where `Delta R` is computed from the rapidity and azimuthal angle
+    - InvariantMass(particles1, particles2, min=0, max=inf): `min <= m_ij <= max` for all pairs of distinct particles
+      from the two groups
+    - Custom(function): user-defined cut, `function` is called with the list of all momenta and returns whether the
+      point is accepted
+
+    Example:
+        ```python
+        jets = [1, -1, 2, -2, 3, -3, 4, -4, 5, -5, 21]
+        proc.cuts = [Cut.PT(jets, min=20), Cut.Rapidity(jets, 4.5), Cut.DeltaR(jets, jets, 0.4)]
+        ```
+    """
+
+    PT: ...
+    Rapidity: ...
+    DeltaR: ...
+    InvariantMass: ...
+    Custom: ...
+
+class Beam:
+    """
+    Beam of a collider. Hadron beams are described by a member of an LHAPDF set in the `lhagrid1` format, which is
+    read and interpolated without the LHAPDF library. Beams without PDF enter the hard process directly.
+    """
+
+    energy: float
+    """Beam energy"""
+    pdg: Optional[int]
+    """PDG id of the beam particle, if given"""
+
+    def __new__(
+        cls, energy: float, pdf: Optional[str] = None, member: int = 0, pdg: Optional[int] = None
+    ) -> Beam:
+        """
+        Parameters:
+            energy: beam energy
+            pdf: path of the LHAPDF set directory (e.g. `.../NNPDF31_nlo_as_0118`) or of a single member `.dat` file
+            member: member of the set, only used if `pdf` is a directory
+            pdg: PDG id of the beam particle written to LHE files (default: `2212` for beams with a PDF, otherwise
+                the incoming parton)
+        """
+
+    def xfx(self, pid: int, x: float, q2: float) -> Optional[float]:
+        """Momentum density `x f(x, Q^2)` of parton `pid`, or `None` for a beam without PDF"""
+
+class Collider:
+    """
+    Collider with two beams colliding head-on along the z-axis
+
+    Example:
+        ```python
+        proton = Beam(6800, "/usr/share/LHAPDF/NNPDF31_nlo_as_0118")
+        lhc = Collider(proton, proton)
+        proc.integrate(0, Scale.Hadronic(lhc, 250**2), 100_000)
+        ```
+    """
+
+    beam1: Beam
+    """Beam moving in positive z-direction"""
+    beam2: Beam
+    """Beam moving in negative z-direction"""
+    factorization_scale: Optional[float]
+    """Fixed factorization scale, or `None` to use the partonic center of mass energy"""
+    sqrt_s: float
+    """Hadronic center of mass energy"""
+
+    def __new__(cls, beam1: Beam, beam2: Beam, factorization_scale: Optional[float] = None) -> Collider: ...
+
+class FourVector:
+    """
+    Four-momentum `(E, p_x, p_y, p_z)` with metric `(+, -, -, -)`
+
+    Example:
+        ```python
+        momenta = proc.sample_array(0, Scale.Fixed(1E6), 1)[0][0]
+        h1, h2 = FourVector(*momenta[2]), FourVector(*momenta[3])
+        print((h1 + h2).m, h1.boost_to_rest_frame(h1 + h2).abs_p)
+        ```
+    """
+
+    e: float
+    px: float
+    py: float
+    pz: float
+    pt: float
+    """Transverse momentum"""
+    abs_p: float
+    """Absolute value of the three-momentum"""
+    eta: float
+    """Pseudorapidity"""
+    rapidity: float
+    phi: float
+    """Azimuthal angle in `(-pi, pi]`"""
+    m: float
+    """Invariant mass, negative for space-like momenta"""
+    m2: float
+    """Invariant mass squared"""
+    beta: list[float]
+    """Velocity `p / E` of the rest frame"""
+
+    def __new__(cls, e: float, px: float, py: float, pz: float) -> FourVector: ...
+    def dot(self, other: FourVector) -> float:
+        """Minkowski product"""
+
+    def delta_r(self, other: FourVector) -> float:
+        """Distance in the rapidity-azimuth plane"""
+
+    def boost(self, beta: list[float]) -> FourVector:
+        """Boost into a frame moving with velocity `-beta`"""
+
+    def boost_to_rest_frame(self, other: FourVector) -> FourVector:
+        """Boost into the rest frame of `other`"""
+
+    def rotate(self, axis: list[float], angle: float) -> FourVector:
+        """Rotate by `angle` around `axis`"""
+
+    def tolist(self) -> list[float]: ...
+    def __add__(self, other: FourVector) -> FourVector: ...
+    def __sub__(self, other: FourVector) -> FourVector: ...
+    def __neg__(self) -> FourVector: ...
+    def __mul__(self, factor: float) -> FourVector: ...
+    def __rmul__(self, factor: float) -> FourVector: ...
+    def __getitem__(self, index: int) -> float: ...
+    def __len__(self) -> int: ...
+
+def pt(p: npt.NDArray[np.float64]) -> npt.NDArray[np.float64]:
+    """Transverse momenta of the momenta `p` of shape `(..., 4)`, returns an array of shape `(...)`"""
+
+def eta(p: npt.NDArray[np.float64]) -> npt.NDArray[np.float64]:
+    """Pseudorapidities of the momenta `p` of shape `(..., 4)`, returns an array of shape `(...)`"""
+
+def rapidity(p: npt.NDArray[np.float64]) -> npt.NDArray[np.float64]:
+    """Rapidities of the momenta `p` of shape `(..., 4)`, returns an array of shape `(...)`"""
+
+def phi(p: npt.NDArray[np.float64]) -> npt.NDArray[np.float64]:
+    """Azimuthal angles of the momenta `p` of shape `(..., 4)`, returns an array of shape `(...)`"""
+
+def mass(p: npt.NDArray[np.float64]) -> npt.NDArray[np.float64]:
+    """Invariant masses of the momenta `p` of shape `(..., 4)`, negative for space-like momenta"""
+
+def delta_r(p: npt.NDArray[np.float64], q: npt.NDArray[np.float64]) -> npt.NDArray[np.float64]:
+    """
+    Distances in the rapidity-azimuth plane of the momenta `p` and `q` of equal shape `(..., 4)`
+
+    Example:
+        ```python
+        momenta, weights = proc.sample_array(0, Scale.Fixed(1E6), 10_000)
+        m_hh = mass(momenta[:, 2] + momenta[:, 3])
+        ```
+    """
@@ -17,7 +17,7 @@ def ufo_model(path: str) -> Model:
                 mass = mass.real
         else:
             raise ValueError(f"Unable to determine mass of particle {p.name}")
-        model.add_particle(p.pdg_code, p.name, mass, p.mass.name)
+        model.add_particle(p.pdg_code, p.name, mass, p.mass.name, p.spin, p.color)
     return model
 
 def load_ufo_files(mpath):
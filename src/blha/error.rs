@@ -12,4 +12,10 @@ pub(crate) enum BLHAError {
     ParseError(String, #[source] peg::error::ParseError<peg::str::LineCol>),
     #[error("Error while loading shared library: {0}")]
     LibraryError(#[from] libloading::Error),
+    #[error("OLP does not provide {0}")]
+    FunctionError(String),
+    #[error("Invalid parameter: {0}")]
+    ParameterError(String),
+    #[error("Invalid order: {0}")]
+    OrderError(String),
 }
@@ -3,7 +3,13 @@
 use std::{
     ffi::{CString, c_char, c_double},
     fmt::Display,
-    path::Path,
+    hash::Hash,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
 };
 
 use indexmap::IndexMap;
@@ -13,7 +19,7 @@ use self_cell::self_cell;
 
 use error::BLHAError;
 
-use crate::{model::Model, util::scalar};
+use crate::{model::Model, result::EvalResult, util::scalar};
 
 pub(crate) mod error;
 pub(crate) mod order_writer;
@@ -30,6 +36,17 @@ pub(crate) enum AmplitudeType {
     LoopInduced,
 }
 
+/// Layout of the values returned by the OLP for `Tree` amplitudes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[pyclass]
+pub(crate) enum TreeLayout {
+    /// The Born matrix element as the only value, as specified by BLHA2
+    Born,
+    /// The layout of `Loop` amplitudes with vanishing poles and finite part and the Born matrix element as the fourth
+    /// value, as returned by GoSam
+    Loop,
+}
+
 impl Display for AmplitudeType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -43,27 +60,176 @@ impl Display for AmplitudeType {
     }
 }
 
-#[derive(Debug, PartialEq, Hash)]
+/// Version of the Binoth Les Houches Accord of the order and contract files
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum InterfaceVersion {
+    /// BLHA1 (0910.3671), with only loop amplitudes evaluated by `OLP_EvalSubProcess`
+    BLHA1,
+    /// BLHA2 (1308.3462), evaluated by `OLP_EvalSubProcess2`
+    BLHA2,
+}
+
+impl FromStr for InterfaceVersion {
+    type Err = BLHAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match s.to_uppercase().as_str() {
+            "BLHA1" => Ok(InterfaceVersion::BLHA1),
+            "BLHA2" => Ok(InterfaceVersion::BLHA2),
+            _ => Err(BLHAError::OrderError(format!(
+                "Unknown interface version {s}"
+            ))),
+        };
+    }
+}
+
+impl Display for InterfaceVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BLHA1 => write!(f, "BLHA1"),
+            Self::BLHA2 => write!(f, "BLHA2"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) struct Subprocess {
-    pub(crate) id: i64,
+    /// OLP ids assigned in the contract, one per channel, empty for subprocesses of the order
+    pub(crate) ids: Vec<i64>,
     pub(crate) amplitude_type: AmplitudeType,
     pub(crate) incoming_pdg: Vec<i64>,
     pub(crate) outgoing_pdg: Vec<i64>,
+    /// `CouplingPower` options in effect when the subprocess was declared in the contract
+    pub(crate) coupling_powers: IndexMap<String, i64>,
+    /// Remaining options in effect when the subprocess was declared in the contract
+    pub(crate) options: IndexMap<String, String>,
+}
+
+impl Hash for Subprocess {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.ids.hash(state);
+        self.amplitude_type.hash(state);
+        self.incoming_pdg.hash(state);
+        self.outgoing_pdg.hash(state);
+        self.coupling_powers.iter().collect::<Vec<_>>().hash(state);
+        self.options.iter().collect::<Vec<_>>().hash(state);
+    }
 }
 
 impl Subprocess {
-    fn n_legs(&self) -> usize {
+    pub(crate) fn n_legs(&self) -> usize {
         self.incoming_pdg.len() + self.outgoing_pdg.len()
     }
+
+    /// Whether the subprocess has the amplitude type `amplitude_type` and the particles `incoming -> outgoing`
+    fn same_process(
+        &self,
+        amplitude_type: AmplitudeType,
+        incoming: &[i64],
+        outgoing: &[i64],
+    ) -> bool {
+        return self.amplitude_type == amplitude_type
+            && self.incoming_pdg == incoming
+            && self.outgoing_pdg == outgoing;
+    }
+
+    /// Whether the subprocess was declared with the coupling powers `coupling_powers`, which may name only some of
+    /// the couplings
+    fn has_coupling_powers(&self, coupling_powers: &IndexMap<String, i64>) -> bool {
+        return coupling_powers
+            .iter()
+            .all(|(coupling, power)| self.coupling_powers.get(coupling) == Some(power));
+    }
 }
 
+/// Subprocess of the order file rejected by the OLP together with its answer
 #[derive(Debug, PartialEq)]
-struct Contract {
+struct Rejected {
+    amplitude_type: AmplitudeType,
+    incoming_pdg: Vec<i64>,
+    outgoing_pdg: Vec<i64>,
+    answer: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct Contract {
     options: IndexMap<String, String>,
     subprocesses: Vec<Subprocess>,
+    rejected: Vec<Rejected>,
+}
+
+impl Contract {
+    /// Interface version of the contract. BLHA1 contracts have no `InterfaceVersion` option.
+    fn interface_version(&self) -> Result<InterfaceVersion, BLHAError> {
+        return match self.options.get("InterfaceVersion") {
+            Some(version) => version.parse(),
+            None => Ok(InterfaceVersion::BLHA1),
+        };
+    }
+
+    /// Match the subprocesses `subprocesses` of the order file to the subprocesses of the contract by their particles
+    /// and amplitude type, and by their coupling powers if the order specifies them, and return the OLP ids of the
+    /// channels of each. Repeated subprocesses are matched to the contract entries in order of their OLP ids.
+    fn match_subprocesses(&self, subprocesses: &[Subprocess]) -> Result<Vec<Vec<i64>>, BLHAError> {
+        let mut used = vec![false; self.subprocesses.len()];
+        let mut ids = Vec::with_capacity(subprocesses.len());
+        for s in subprocesses.iter() {
+            let (atype, incoming, outgoing) = (s.amplitude_type, &s.incoming_pdg, &s.outgoing_pdg);
+            let matched = self.subprocesses.iter().enumerate().find(|(i, c)| {
+                !used[*i]
+                    && c.same_process(atype, incoming, outgoing)
+                    && c.has_coupling_powers(&s.coupling_powers)
+            });
+            if let Some((i, c)) = matched {
+                used[i] = true;
+                ids.push(c.ids.clone());
+                continue;
+            }
+            let mut process = format!(
+                "{} -> {} ({atype})",
+                incoming
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+                outgoing
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            );
+            if !s.coupling_powers.is_empty() {
+                process += &format!(" with coupling powers {:?}", s.coupling_powers);
+            }
+            if let Some(rejected) = self.rejected.iter().find(|r| {
+                r.amplitude_type == atype
+                    && &r.incoming_pdg == incoming
+                    && &r.outgoing_pdg == outgoing
+            }) {
+                return Err(BLHAError::ContractError(format!(
+                    "Subprocess {process} was rejected: {}",
+                    rejected.answer
+                )));
+            }
+            return Err(BLHAError::ContractError(format!(
+                "Subprocess {process} is missing in the contract"
+            )));
+        }
+        return Ok(ids);
+    }
+
+    /// Subprocess with a channel with OLP id `id`
+    fn subprocess(&self, id: i64) -> &Subprocess {
+        return self
+            .subprocesses
+            .iter()
+            .find(|s| s.ids.contains(&id))
+            .expect("OLP ids are taken from the contract");
+    }
 }
 
 pub(crate) struct Order<'a> {
+    pub(crate) interface_version: InterfaceVersion,
     pub(crate) model: &'a Model,
     pub(crate) coupling_orders: IndexMap<String, usize>,
     pub(crate) nlo_coupling: Option<String>,
@@ -71,22 +237,44 @@ pub(crate) struct Order<'a> {
     pub(crate) subprocesses: &'a Vec<Subprocess>,
 }
 
+/// Evaluation function of the OLP, depending on the interface version
+enum EvalFunction<'a> {
+    /// `OLP_EvalSubProcess(label, momenta, mu, parameters, results)`, where `parameters` contains the strong coupling
+    BLHA1(
+        Symbol<
+            'a,
+            unsafe extern "C" fn(
+                *const i32,
+                *const c_double,
+                *const c_double,
+                *const c_double,
+                *mut c_double,
+            ),
+        >,
+    ),
+    /// `OLP_EvalSubProcess2(label, momenta, mu, results, accuracy)`
+    BLHA2(
+        Symbol<
+            'a,
+            unsafe extern "C" fn(
+                *const i32,
+                *const c_double,
+                *const c_double,
+                *mut c_double,
+                *mut c_double,
+            ),
+        >,
+    ),
+}
+
 struct BLHAInterface<'a> {
     start: Symbol<'a, unsafe extern "C" fn(*const c_char, *mut i32)>,
-    info: Symbol<'a, unsafe extern "C" fn(*const c_char, *const c_char, *mut c_char)>,
-    set_parameter:
+    info: Option<Symbol<'a, unsafe extern "C" fn(*const c_char, *const c_char, *mut c_char)>>,
+    set_parameter: Option<
         Symbol<'a, unsafe extern "C" fn(*const c_char, *const c_double, *const c_double, *mut i32)>,
-    print_parameters: Symbol<'a, unsafe extern "C" fn(*const c_char)>,
-    eval: Symbol<
-        'a,
-        unsafe extern "C" fn(
-            *const i32,
-            *const c_double,
-            *const c_double,
-            *mut c_double,
-            *mut c_double,
-        ),
     >,
+    print_parameters: Option<Symbol<'a, unsafe extern "C" fn(*const c_char)>>,
+    eval: EvalFunction<'a>,
 }
 
 self_cell!(
@@ -99,12 +287,28 @@ self_cell!(
 
 pub(crate) struct OneLoopProvider {
     contract: Contract,
+    contract_path: PathBuf,
+    library_path: PathBuf,
+    /// Strong coupling passed to `OLP_EvalSubProcess` of BLHA1, NaN until it is set
+    alpha_s: AtomicU64,
+    eval_lock: Mutex<()>,
+    tree_layout: TreeLayout,
+    isolated_copies: bool,
     lib: OLPLibrary,
 }
 
 impl OneLoopProvider {
-    pub(crate) fn new(contract_path: &Path, library_path: &Path) -> Result<Self, BLHAError> {
+    /// Load the library `library_path` and start it with the contract `contract_path`. `Tree` results are returned
+    /// in the layout `tree_layout`, and `isolated_copies` states whether copies of the library loaded by `load_copy`
+    /// share no state, i.e. the library does not load further libraries with global state.
+    pub(crate) fn new(
+        contract_path: &Path,
+        library_path: &Path,
+        tree_layout: TreeLayout,
+        isolated_copies: bool,
+    ) -> Result<Self, BLHAError> {
         let contract = parser::parse_contract(contract_path)?;
+        let version = contract.interface_version()?;
         let library;
         unsafe {
             library = Library::new(library_path)?;
@@ -112,13 +316,31 @@ impl OneLoopProvider {
 
         let olp = Self {
             contract,
+            contract_path: contract_path.to_path_buf(),
+            library_path: library_path.to_path_buf(),
+            alpha_s: AtomicU64::new(f64::NAN.to_bits()),
+            eval_lock: Mutex::new(()),
+            tree_layout,
+            isolated_copies,
             lib: OLPLibrary::try_new(library, |lib| -> Result<BLHAInterface<'_>, BLHAError> {
+                // BLHA1 does not require the functions for parameters and information
+                let set_parameter = unsafe { lib.get(b"OLP_SetParameter") };
                 Ok(BLHAInterface {
                     start: unsafe { lib.get(b"OLP_Start")? },
-                    info: unsafe { lib.get(b"OLP_Info")? },
-                    set_parameter: unsafe { lib.get(b"OLP_SetParameter")? },
-                    print_parameters: unsafe { lib.get(b"OLP_PrintParameter")? },
-                    eval: unsafe { lib.get(b"OLP_EvalSubProcess2")? },
+                    info: unsafe { lib.get(b"OLP_Info").ok() },
+                    set_parameter: match version {
+                        InterfaceVersion::BLHA1 => set_parameter.ok(),
+                        InterfaceVersion::BLHA2 => Some(set_parameter?),
+                    },
+                    print_parameters: unsafe { lib.get(b"OLP_PrintParameter").ok() },
+                    eval: match version {
+                        InterfaceVersion::BLHA1 => {
+                            EvalFunction::BLHA1(unsafe { lib.get(b"OLP_EvalSubProcess")? })
+                        }
+                        InterfaceVersion::BLHA2 => {
+                            EvalFunction::BLHA2(unsafe { lib.get(b"OLP_EvalSubProcess2")? })
+                        }
+                    },
                 })
             })?,
         };
@@ -137,16 +359,144 @@ impl OneLoopProvider {
         }
         return Ok(());
     }
+}
 
-    pub(crate) fn set_parameter(
-        &self,
-        parameter: &str,
-        real: f64,
-        imag: f64,
-    ) -> Result<(), BLHAError> {
+/// One-loop provider evaluating the subprocesses assigned in a BLHA contract
+#[allow(clippy::upper_case_acronyms)]
+pub(crate) trait OLP: Send + Sync {
+    /// Contract of the OLP
+    fn contract(&self) -> &Contract;
+
+    /// Lock serializing the evaluations of this instance, since the OLP keeps its parameters in global state. It is
+    /// held while a rescue step temporarily switches parameters, such that no other evaluation sees them.
+    fn eval_lock(&self) -> &Mutex<()>;
+
+    /// Load an independent instance of the OLP, which can be evaluated in parallel to this one. `dir` is a scratch
+    /// directory for the `index`-th instance.
+    fn load_copy(&self, dir: &Path, index: usize) -> Result<Box<dyn OLP>, BLHAError>;
+
+    /// Whether instances loaded by `load_copy` are isolated from each other and can be evaluated in parallel
+    fn isolated_copies(&self) -> bool {
+        return false;
+    }
+
+    fn set_parameter(&self, parameter: &str, real: f64, imag: f64) -> Result<(), BLHAError>;
+
+    fn print_parameters(&self, filename: &str) -> Result<(), BLHAError>;
+
+    /// Evaluate the subprocess with OLP id `id` at the point `momenta`
+    fn eval(&self, id: i64, momenta: &[[f64; 4]], scale: f64) -> Result<EvalResult, BLHAError>;
+
+    /// Value of the option `option` in the contract, if present
+    fn contract_option(&self, option: &str) -> Option<&str> {
+        return self
+            .contract()
+            .options
+            .get(option)
+            .map(|value| value.as_str());
+    }
+
+    /// Subprocesses of the contract, ordered by their OLP ids
+    fn subprocesses(&self) -> &[Subprocess] {
+        return &self.contract().subprocesses;
+    }
+
+    /// Whether the OLP reports the accuracy of its results, which BLHA1 OLPs do not
+    fn reports_accuracy(&self) -> bool {
+        return matches!(
+            self.contract().interface_version(),
+            Ok(InterfaceVersion::BLHA2)
+        );
+    }
+
+    /// The `AccuracyTarget` requested in the contract, if any
+    fn accuracy_target(&self) -> Option<f64> {
+        return self
+            .contract_option("AccuracyTarget")
+            .and_then(|target| target.parse().ok());
+    }
+
+    /// OLP ids of the channels assigned in the contract to the subprocesses `subprocesses` of the order file, see
+    /// `Contract::match_subprocesses`
+    fn olp_ids(&self, subprocesses: &[Subprocess]) -> Result<Vec<Vec<i64>>, BLHAError> {
+        return self.contract().match_subprocesses(subprocesses);
+    }
+
+    /// Subprocess of the contract with a channel with OLP id `id`
+    fn subprocess(&self, id: i64) -> &Subprocess {
+        return self.contract().subprocess(id);
+    }
+
+    /// Number of values of the results of the subprocess with OLP id `id`, depending on its amplitude type. `Tree`
+    /// results always have the layout of `Loop` results.
+    fn n_results(&self, id: i64) -> usize {
+        let subprocess = self.subprocess(id);
+        return match subprocess.amplitude_type {
+            AmplitudeType::Tree | AmplitudeType::LoopInduced => 4,
+            AmplitudeType::Loop => 4,
+            AmplitudeType::ccTree => {
+                let n = subprocess.n_legs();
+                n * (n - 1) / 2
+            }
+            AmplitudeType::scTree | AmplitudeType::scTree2 => {
+                let n = subprocess.n_legs();
+                2 * n * n
+            }
+        };
+    }
+}
+
+impl OLP for OneLoopProvider {
+    fn contract(&self) -> &Contract {
+        return &self.contract;
+    }
+
+    fn eval_lock(&self) -> &Mutex<()> {
+        return &self.eval_lock;
+    }
+
+    /// Load an independent instance of the process library. Since the OLP keeps its state in global variables, the
+    /// library is copied to `dir` first to force the dynamic loader to map a separate copy.
+    fn load_copy(&self, dir: &Path, index: usize) -> Result<Box<dyn OLP>, BLHAError> {
+        let copy_path = dir.join(format!(
+            "{}_{index}.so",
+            self.library_path.file_stem().unwrap().to_str().unwrap()
+        ));
+        if let Err(e) = std::fs::copy(&self.library_path, &copy_path) {
+            return Err(BLHAError::IOError(
+                copy_path.to_str().unwrap().to_owned(),
+                e,
+            ));
+        }
+        return Ok(Box::new(Self::new(
+            &self.contract_path,
+            &copy_path,
+            self.tree_layout,
+            self.isolated_copies,
+        )?));
+    }
+
+    fn isolated_copies(&self) -> bool {
+        return self.isolated_copies;
+    }
+
+    fn set_parameter(&self, parameter: &str, real: f64, imag: f64) -> Result<(), BLHAError> {
+        // The strong coupling is an argument of every evaluation in BLHA1
+        let is_alpha_s = matches!(parameter.to_lowercase().as_str(), "alphas" | "alpha_s");
+        if is_alpha_s {
+            self.alpha_s.store(real.to_bits(), Ordering::Relaxed);
+        }
+        let Some(ref set_parameter) = self.lib.borrow_dependent().set_parameter else {
+            if is_alpha_s {
+                return Ok(());
+            }
+            return Err(BLHAError::FunctionError(format!(
+                "OLP_SetParameter to set {parameter}"
+            )));
+        };
         let mut ierr: i32 = 1;
         unsafe {
-            (self.lib.borrow_dependent().set_parameter)(
+            set_parameter(
                 CString::new(parameter).unwrap().as_ptr(),
                 &real as *const f64,
                 &imag as *const f64,
@@ -159,33 +509,18 @@ impl OneLoopProvider {
         return Ok(());
     }
 
-    pub(crate) fn print_parameters(&self, filename: &str) {
+    fn print_parameters(&self, filename: &str) -> Result<(), BLHAError> {
+        let Some(ref print_parameters) = self.lib.borrow_dependent().print_parameters else {
+            return Err(BLHAError::FunctionError("OLP_PrintParameter".to_owned()));
+        };
         unsafe {
-            (self.lib.borrow_dependent().print_parameters)(
-                CString::new(filename).unwrap().as_ptr(),
-            );
+            print_parameters(CString::new(filename).unwrap().as_ptr());
         }
+        return Ok(());
     }
 
-    pub(crate) fn eval(
-        &self,
-        id: usize,
-        momenta: &[[f64; 4]],
-        scale: f64,
-    ) -> Result<Vec<f64>, BLHAError> {
-        let n_results = match self.contract.subprocesses[id].amplitude_type {
-            AmplitudeType::Tree | AmplitudeType::LoopInduced => 4,
-            AmplitudeType::Loop => 4,
-            AmplitudeType::ccTree => {
-                let n = self.contract.subprocesses[id].n_legs();
-                n * (n - 1) / 2
-            }
-            AmplitudeType::scTree | AmplitudeType::scTree2 => {
-                let n = self.contract.subprocesses[id].n_legs();
-                2 * n * n
-            }
-        };
-        let mut res = vec![0.; n_results];
+    fn eval(&self, id: i64, momenta: &[[f64; 4]], scale: f64) -> Result<EvalResult, BLHAError> {
+        let mut res = vec![0.; self.n_results(id)];
         let mut momenta_flat = vec![0.; 5 * momenta.len()];
         for (i, momentum) in momenta.iter().enumerate() {
             for j in 0..=4 {
@@ -196,16 +531,43 @@ impl OneLoopProvider {
                 }
             }
         }
-        let mut precision = 0.;
-        unsafe {
-            (self.lib.borrow_dependent().eval)(
-                &(id as i32) as *const i32,
-                momenta_flat.as_ptr(),
-                &scale as *const f64,
-                res.as_mut_ptr(),
-                &mut precision as *mut f64,
-            )
+        // BLHA1 reports no accuracy, which is therefore unknown
+        let mut precision = f64::NAN;
+        match self.lib.borrow_dependent().eval {
+            EvalFunction::BLHA1(ref eval) => {
+                let alpha_s = f64::from_bits(self.alpha_s.load(Ordering::Relaxed));
+                if alpha_s.is_nan() {
+                    return Err(BLHAError::ParameterError(
+                        "BLHA1 requires the strong coupling, set it with set_parameter(\"alphas\", ...)"
+                            .to_owned(),
+                    ));
+                }
+                let parameters = [alpha_s];
+                unsafe {
+                    eval(
+                        &(id as i32) as *const i32,
+                        momenta_flat.as_ptr(),
+                        &scale as *const f64,
+                        parameters.as_ptr(),
+                        res.as_mut_ptr(),
+                    )
+                }
+            }
+            EvalFunction::BLHA2(ref eval) => unsafe {
+                eval(
+                    &(id as i32) as *const i32,
+                    momenta_flat.as_ptr(),
+                    &scale as *const f64,
+                    res.as_mut_ptr(),
+                    &mut precision as *mut f64,
+                )
+            },
+        }
+        if self.tree_layout == TreeLayout::Born
+            && self.subprocess(id).amplitude_type == AmplitudeType::Tree
+        {
+            res = vec![0., 0., 0., res[0]];
         }
-        return Ok(res);
+        return Ok(EvalResult::new(res, precision));
     }
 }
@@ -1,10 +1,119 @@
 use std::io::prelude::*;
 use std::path::Path;
 
-use super::{AmplitudeType, Order};
+use indexmap::IndexMap;
 
-pub(crate) fn write_order_file(order: &Order, path: &Path) -> Result<(), std::io::Error> {
-    let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+use super::{AmplitudeType, InterfaceVersion, Order, Subprocess, error::BLHAError};
+
+pub(crate) fn write_order_file(order: &Order, path: &Path) -> Result<(), BLHAError> {
+    if order.interface_version == InterfaceVersion::BLHA1 {
+        check_blha1(order)?;
+    }
+    let write = || -> Result<(), std::io::Error> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        return match order.interface_version {
+            InterfaceVersion::BLHA1 => write_blha1(order, &mut writer),
+            InterfaceVersion::BLHA2 => write_blha2(order, &mut writer),
+        };
+    };
+    return write().map_err(|e| BLHAError::IOError(path.to_str().unwrap().to_owned(), e));
+}
+
+/// BLHA1 only knows loop amplitudes and the strong and electroweak couplings
+fn check_blha1(order: &Order) -> Result<(), BLHAError> {
+    if let Some(s) = order
+        .subprocesses
+        .iter()
+        .find(|s| s.amplitude_type != AmplitudeType::Loop)
+    {
+        return Err(BLHAError::OrderError(format!(
+            "BLHA1 does not support the amplitude type {}",
+            s.amplitude_type
+        )));
+    }
+    if let Some(coupling) = order
+        .coupling_orders
+        .keys()
+        .chain(
+            order
+                .subprocesses
+                .iter()
+                .flat_map(|s| s.coupling_powers.keys()),
+        )
+        .find(|c| blha1_power(c).is_none())
+    {
+        return Err(BLHAError::OrderError(format!(
+            "BLHA1 does not support the coupling {coupling}"
+        )));
+    }
+    return Ok(());
+}
+
+/// BLHA1 option for the power of `coupling`
+fn blha1_power(coupling: &str) -> Option<&'static str> {
+    return match coupling.to_uppercase().as_str() {
+        "QCD" => Some("AlphasPower"),
+        "QED" => Some("AlphaPower"),
+        _ => None,
+    };
+}
+
+/// Coupling powers of subprocess `s`, i.e. the coupling orders of the order overridden by those of `s`
+fn coupling_powers<'a>(order: &'a Order, s: &'a Subprocess) -> IndexMap<&'a str, i64> {
+    let mut powers = order
+        .coupling_orders
+        .iter()
+        .map(|(coupling, power)| (coupling.as_str(), *power as i64))
+        .collect::<IndexMap<_, _>>();
+    powers.extend(s.coupling_powers.iter().map(|(c, p)| (c.as_str(), *p)));
+    return powers;
+}
+
+/// Write the coupling powers of the subprocesses, which stay in effect for the following subprocesses until they
+/// are overwritten, and the subprocesses with `write_subprocess`
+fn write_subprocesses<W: Write>(
+    order: &Order,
+    writer: &mut W,
+    coupling_power: impl Fn(&str, i64) -> String,
+    mut write_subprocess: impl FnMut(&Subprocess, &mut W) -> Result<(), std::io::Error>,
+) -> Result<(), std::io::Error> {
+    let mut in_effect = IndexMap::new();
+    for (coupling, power) in order.coupling_orders.iter() {
+        writeln!(writer, "{}", coupling_power(coupling, *power as i64))?;
+        in_effect.insert(coupling.as_str(), *power as i64);
+    }
+    for s in order.subprocesses.iter() {
+        let powers = coupling_powers(order, s);
+        for (coupling, power) in powers.iter() {
+            if in_effect.get(coupling) != Some(power) {
+                writeln!(writer, "{}", coupling_power(coupling, *power))?;
+            }
+        }
+        in_effect = powers;
+        write_subprocess(s, writer)?;
+    }
+    return Ok(());
+}
+
+fn write_blha1(order: &Order, writer: &mut impl Write) -> Result<(), std::io::Error> {
+    writeln!(
+        writer,
+        r#"# BLHA1 order automatically generated by pygs
+IRregularisation CDR"#
+    )?;
+    writeln!(writer, "Model ufo:/{}", order.model.path.to_str().unwrap())?;
+    for (option, value) in order.options.iter() {
+        writeln!(writer, "{option} {value}")?;
+    }
+    return write_subprocesses(
+        order,
+        writer,
+        |coupling, power| format!("{} {power}", blha1_power(coupling).unwrap()),
+        |s, writer| write_subprocess(s, writer),
+    );
+}
+
+fn write_blha2(order: &Order, writer: &mut impl Write) -> Result<(), std::io::Error> {
     writeln!(
         writer,
         r#"# BLHA2 order automatically generated by pygs
@@ -15,25 +124,140 @@ IRregularisation CDR"#
     for (option, value) in order.options.iter() {
         writeln!(writer, "{option} {value}")?;
     }
-    for (coupling, value) in order.coupling_orders.iter() {
-        writeln!(writer, "CouplingPower {coupling} {}", value)?;
+    return write_subprocesses(
+        order,
+        writer,
+        |coupling, power| format!("CouplingPower {coupling} {power}"),
+        |s, writer| {
+            writeln!(writer, "AmplitudeType {}", s.amplitude_type)?;
+            return write_subprocess(s, writer);
+        },
+    );
+}
+
+fn write_subprocess(s: &Subprocess, writer: &mut impl Write) -> Result<(), std::io::Error> {
+    return writeln!(
+        writer,
+        "{} -> {}",
+        s.incoming_pdg
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(" "),
+        s.outgoing_pdg
+            .iter()
+            .map(|i| i.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Model;
+
+    #[test]
+    fn blha1_order_test() {
+        let model = Model::new("/models/sm".into());
+        let subprocess = |amplitude_type| Subprocess {
+            ids: vec![],
+            amplitude_type,
+            incoming_pdg: vec![21, 21],
+            outgoing_pdg: vec![6, -6],
+            coupling_powers: IndexMap::new(),
+            options: IndexMap::new(),
+        };
+        let subprocesses = vec![subprocess(AmplitudeType::Loop)];
+        let mut order = Order {
+            interface_version: InterfaceVersion::BLHA1,
+            coupling_orders: IndexMap::from([("QCD".to_owned(), 2)]),
+            model: &model,
+            nlo_coupling: None,
+            subprocesses: &subprocesses,
+            options: IndexMap::from([("CorrectionType".to_owned(), "QCD".to_owned())]),
+        };
+        let path = std::env::temp_dir().join(format!("pygs_blha1_{}.olp", std::process::id()));
+        write_order_file(&order, &path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(
+            content,
+            r#"# BLHA1 order automatically generated by pygs
+IRregularisation CDR
+Model ufo://models/sm
+CorrectionType QCD
+AlphasPower 2
+21 21 -> 6 -6
+"#
+        );
+
+        order.coupling_orders.insert("QNP".to_owned(), 1);
+        assert!(matches!(
+            write_order_file(&order, &path),
+            Err(BLHAError::OrderError(_))
+        ));
+        let subprocesses = vec![subprocess(AmplitudeType::Tree)];
+        order.coupling_orders.shift_remove("QNP");
+        order.subprocesses = &subprocesses;
+        assert!(matches!(
+            write_order_file(&order, &path),
+            Err(BLHAError::OrderError(_))
+        ));
     }
-    for s in order.subprocesses.iter() {
-        writeln!(writer, "AmplitudeType {}", s.amplitude_type)?;
-        writeln!(
-            writer,
-            "{} -> {}",
-            s.incoming_pdg
-                .iter()
-                .map(|i| i.to_string())
-                .collect::<Vec<_>>()
-                .join(" "),
-            s.outgoing_pdg
+
+    #[test]
+    fn coupling_powers_test() {
+        let model = Model::new("/models/sm".into());
+        let subprocess = |coupling_powers: &[(&str, i64)]| Subprocess {
+            ids: vec![],
+            amplitude_type: AmplitudeType::Loop,
+            incoming_pdg: vec![21, 21],
+            outgoing_pdg: vec![25],
+            coupling_powers: coupling_powers
                 .iter()
-                .map(|i| i.to_string())
-                .collect::<Vec<_>>()
-                .join(" ")
-        )?;
+                .map(|(c, p)| (c.to_string(), *p))
+                .collect(),
+            options: IndexMap::new(),
+        };
+        let subprocesses = vec![
+            subprocess(&[]),
+            subprocess(&[("QCD", 3)]),
+            subprocess(&[("QCD", 3), ("QED", 1)]),
+            subprocess(&[]),
+        ];
+        let order = Order {
+            interface_version: InterfaceVersion::BLHA2,
+            coupling_orders: IndexMap::from([("QCD".to_owned(), 2), ("QED".to_owned(), 1)]),
+            model: &model,
+            nlo_coupling: None,
+            subprocesses: &subprocesses,
+            options: IndexMap::new(),
+        };
+        let path = std::env::temp_dir().join(format!("pygs_powers_{}.olp", std::process::id()));
+        write_order_file(&order, &path).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        // Overridden powers are restored for the following subprocesses
+        assert_eq!(
+            content,
+            r#"# BLHA2 order automatically generated by pygs
+InterfaceVersion BLHA2
+IRregularisation CDR
+Model ufo://models/sm
+CouplingPower QCD 2
+CouplingPower QED 1
+AmplitudeType Loop
+21 21 -> 25
+CouplingPower QCD 3
+AmplitudeType Loop
+21 21 -> 25
+AmplitudeType Loop
+21 21 -> 25
+CouplingPower QCD 2
+AmplitudeType Loop
+21 21 -> 25
+"#
+        );
     }
-    Ok(())
 }
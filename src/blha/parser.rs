@@ -1,9 +1,9 @@
 #![allow(unused)]
 
-use super::{AmplitudeType, Contract, Subprocess, error::BLHAError};
+use super::{AmplitudeType, Contract, Rejected, Subprocess, error::BLHAError};
 use indexmap::IndexMap;
 use peg::parser;
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, path::Path, str::FromStr};
 
 #[derive(Debug, Clone)]
 enum Value<'a> {
@@ -47,7 +47,7 @@ impl<'a> Value<'a> {
 
 enum Statement<'a> {
     Option(&'a str, Value<'a>),
-    SubProcess(i64, i64, Vec<i64>, Vec<i64>),
+    SubProcess(Result<Vec<i64>, &'a str>, Vec<i64>, Vec<i64>),
 }
 
 peg::parser!(
@@ -55,6 +55,7 @@ peg::parser!(
     rule whitespace() = quiet!{[' ' | '\t' | '\n' | '\r']}
         rule comment() = quiet!{"#" [^'\n']* "\n"}
         rule _() = quiet!{(comment() / whitespace())*}
+        rule inline_whitespace() = quiet!{[' ' | '\t']*}
         rule alphanumeric() = quiet!{['a'..='z' | 'A'..='Z' | '0'..='9']}
         rule ident_char() = quiet!{alphanumeric() / ['_' | '~']}
 
@@ -76,21 +77,27 @@ peg::parser!(
 
         rule option() -> Result<Statement<'input>, BLHAError> =
             option:str() _ values:((!"|" v:value() {v}) **<1,> _) _ "|" _ answer:$([^'\n']+) {?
-                if answer.to_lowercase().as_str() == "ok" {
+                if answer.trim().to_lowercase().as_str() == "ok" {
                     return Ok(Ok(
                         Statement::Option(option.str()?, if values.len() == 1 {values[0].clone()} else {Value::List(values)})
                     ));
                 } else {
-                    return Ok(Err(BLHAError::ContractError(answer.to_owned())));
+                    return Ok(Err(BLHAError::ContractError(answer.trim().to_owned())));
                 }
             }
 
+        rule subprocess_answer() -> Result<Vec<i64>, &'input str> =
+            n:int() ids:(inline_whitespace() id:int() {id})+ {?
+                let ids = ids.into_iter().map(|i| i.int()).collect::<Result<Vec<_>, _>>()?;
+                if n.int()? == ids.len() as i64 { Ok(Ok(ids)) } else { Err("ids") }
+            }
+            / answer:$([^'\n']+) { Err(answer.trim()) }
+
         rule subprocess() -> Statement<'input> =
-            inc:(int() **<1,> _) _ "->" _ out:(int() **<1,> _) _ "|" _ n:int() _ id:int() _ {?
+            inc:(int() **<1,> _) _ "->" _ out:(int() **<1,> _) _ "|" inline_whitespace() answer:subprocess_answer() _ {?
                 return Ok(
                     Statement::SubProcess(
-                        id.int()?,
-                        n.int()?,
+                        answer,
                         inc.into_iter().map(|i| i.int()).collect::<Result<_, _>>()?,
                         out.into_iter().map(|i| i.int()).collect::<Result<_, _>>()?,
                 ));
@@ -99,8 +106,12 @@ peg::parser!(
         pub rule contract() -> Result<Contract, BLHAError> =
             _ statements:( ( (s:subprocess() {Ok(s)}) / (o:option() {o}) ) ** _ ) _ {?
                 let mut subprocesses = Vec::new();
+                let mut rejected = Vec::new();
                 let mut options = IndexMap::new();
                 let mut atype = AmplitudeType::Loop;
+                // Options in effect for the following subprocesses
+                let mut coupling_powers = IndexMap::new();
+                let mut subprocess_options = IndexMap::new();
                 for statement in statements {
                     match statement {
                         Ok(Statement::Option(option, value)) => {
@@ -121,32 +132,82 @@ peg::parser!(
                                     };
                                     options.insert(option.to_owned(), value.to_string());
                                 }
-                                _ => { options.insert(option.to_owned(), value.to_string()); }
+                                "couplingpower" => {
+                                    let Value::List(ref power) = value else {
+                                        return Ok(Err(BLHAError::ContractError(
+                                            format!("Invalid coupling power: {}", value.to_string())
+                                        )));
+                                    };
+                                    let [coupling, power] = &power[..] else {
+                                        return Ok(Err(BLHAError::ContractError(
+                                            format!("Invalid coupling power: {}", value.to_string())
+                                        )));
+                                    };
+                                    coupling_powers.insert(coupling.clone().str()?.to_owned(), power.clone().int()?);
+                                    options.insert(option.to_owned(), value.to_string());
+                                }
+                                // BLHA1 coupling powers
+                                "alphaspower" => {
+                                    coupling_powers.insert("QCD".to_owned(), value.clone().int()?);
+                                    options.insert(option.to_owned(), value.to_string());
+                                }
+                                "alphapower" => {
+                                    coupling_powers.insert("QED".to_owned(), value.clone().int()?);
+                                    options.insert(option.to_owned(), value.to_string());
+                                }
+                                _ => {
+                                    subprocess_options.insert(option.to_owned(), value.clone().to_string());
+                                    options.insert(option.to_owned(), value.to_string());
+                                }
                             }
                         },
-                        Ok(Statement::SubProcess(id, n_hel, incoming, outgoing)) => {
+                        Ok(Statement::SubProcess(Ok(ids), incoming, outgoing)) => {
                             subprocesses.push(
                                 Subprocess {
-                                    id,
+                                    ids,
                                     amplitude_type: atype,
                                     incoming_pdg: incoming,
-                                    outgoing_pdg: outgoing
+                                    outgoing_pdg: outgoing,
+                                    coupling_powers: coupling_powers.clone(),
+                                    options: subprocess_options.clone(),
+                                }
+                            )
+                        },
+                        Ok(Statement::SubProcess(Err(answer), incoming, outgoing)) => {
+                            rejected.push(
+                                Rejected {
+                                    amplitude_type: atype,
+                                    incoming_pdg: incoming,
+                                    outgoing_pdg: outgoing,
+                                    answer: answer.to_owned(),
                                 }
                             )
                         },
                         Err(e) => {return Ok(Err(e));}
                     }
                 }
-                subprocesses.sort_unstable_by_key(|s| s.id);
+                subprocesses.sort_unstable_by_key(|s| s.ids[0]);
                 return Ok(Ok(Contract {
                     options,
-                    subprocesses
+                    subprocesses,
+                    rejected,
                 }));
             }
 
     }
 );
 
+impl FromStr for Contract {
+    type Err = BLHAError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        return match blha_contract::contract(s) {
+            Ok(c) => c,
+            Err(e) => Err(BLHAError::ParseError("contract".to_owned(), e)),
+        };
+    }
+}
+
 pub(crate) fn parse_contract(path: &Path) -> Result<Contract, BLHAError> {
     let content = match std::fs::read_to_string(path) {
         Ok(s) => s,
@@ -199,86 +260,232 @@ CouplingPower QED 0 | OK
 21 -1 -> 6 -6 -1 | 1 6
 21 21 -> 6 -6 21 | 1 7"#;
         let contract = super::blha_contract::contract(&content).unwrap().unwrap();
+        let options = IndexMap::from([
+            ("InterfaceVersion".to_owned(), "BLHA2".to_owned()),
+            ("Model".to_owned(), "SMdiag".to_owned()),
+            ("MatrixElementSquareType".to_owned(), "CHsummed".to_owned()),
+            ("CorrectionType".to_owned(), "QCD".to_owned()),
+            ("IRregularisation".to_owned(), "DRED".to_owned()),
+            ("WidthScheme".to_owned(), "ComplexMass".to_owned()),
+            ("EWScheme".to_owned(), "alphaGF".to_owned()),
+            ("AccuracyTarget".to_owned(), "0.0001".to_owned()),
+            ("DebugUnstable".to_owned(), "True".to_owned()),
+            ("Extra".to_owned(), "Line2".to_owned()),
+        ]);
+        let mut contract_options = options.clone();
+        contract_options.insert(
+            "CouplingPower".to_owned(),
+            "[String(\"QED\"), Int(0)]".to_owned(),
+        );
         let contract_ref = Contract {
-            options: IndexMap::from([
-                ("InterfaceVersion".to_owned(), "BLHA2".to_owned()),
-                ("Model".to_owned(), "SMdiag".to_owned()),
-                ("MatrixElementSquareType".to_owned(), "CHsummed".to_owned()),
-                ("CorrectionType".to_owned(), "QCD".to_owned()),
-                ("IRregularisation".to_owned(), "DRED".to_owned()),
-                ("WidthScheme".to_owned(), "ComplexMass".to_owned()),
-                ("EWScheme".to_owned(), "alphaGF".to_owned()),
-                ("AccuracyTarget".to_owned(), "0.0001".to_owned()),
-                ("DebugUnstable".to_owned(), "True".to_owned()),
-                ("Extra".to_owned(), "Line2".to_owned()),
-                (
-                    "CouplingPower".to_owned(),
-                    "[String(\"QED\"), Int(0)]".to_owned(),
-                ),
-            ]),
+            options: contract_options,
             subprocesses: vec![
                 Subprocess {
-                    id: 1,
+                    ids: vec![1],
                     amplitude_type: Loop,
                     incoming_pdg: vec![1, -1],
                     outgoing_pdg: vec![6, -6, 21],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 3), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 2,
+                    ids: vec![2],
                     amplitude_type: Loop,
                     incoming_pdg: vec![-1, 1],
                     outgoing_pdg: vec![6, -6],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 2), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 3,
+                    ids: vec![3],
                     amplitude_type: Loop,
                     incoming_pdg: vec![1, 21],
                     outgoing_pdg: vec![6, -6, 1],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 3), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 4,
+                    ids: vec![4],
                     amplitude_type: Loop,
                     incoming_pdg: vec![1, -1],
                     outgoing_pdg: vec![6, -6],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 2), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 5,
+                    ids: vec![5],
                     amplitude_type: Loop,
                     incoming_pdg: vec![21, 21],
                     outgoing_pdg: vec![6, -6],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 2), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 6,
+                    ids: vec![6],
                     amplitude_type: Loop,
                     incoming_pdg: vec![21, -1],
                     outgoing_pdg: vec![6, -6, -1],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 3), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 7,
+                    ids: vec![7],
                     amplitude_type: Loop,
                     incoming_pdg: vec![21, 21],
                     outgoing_pdg: vec![6, -6, 21],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 3), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 8,
+                    ids: vec![8],
                     amplitude_type: Loop,
                     incoming_pdg: vec![-1, 1],
                     outgoing_pdg: vec![6, -6, 21],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 3), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 9,
+                    ids: vec![9],
                     amplitude_type: Loop,
                     incoming_pdg: vec![-1, 21],
                     outgoing_pdg: vec![6, -6, -1],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 3), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
                 Subprocess {
-                    id: 10,
+                    ids: vec![10],
                     amplitude_type: Loop,
                     incoming_pdg: vec![21, 1],
                     outgoing_pdg: vec![6, -6, 1],
+                    coupling_powers: IndexMap::from([("QCD".to_owned(), 3), ("QED".to_owned(), 0)]),
+                    options: options.clone(),
                 },
             ],
+            rejected: vec![],
         };
         assert_eq!(contract, contract_ref);
     }
+
+    #[test]
+    fn subprocess_matching_test() {
+        let content = r#"InterfaceVersion BLHA2 | OK
+AmplitudeType Tree | OK
+21 21 -> 6 -6 | 1 1
+AmplitudeType Loop | OK
+21 21 -> 6 -6 | 1 0
+2 -2 -> 6 -6 | Error: process not available
+21 21 -> 6 -6 | 2 2 3
+"#;
+        let contract = super::blha_contract::contract(content).unwrap().unwrap();
+        assert_eq!(
+            contract.rejected,
+            [Rejected {
+                amplitude_type: Loop,
+                incoming_pdg: vec![2, -2],
+                outgoing_pdg: vec![6, -6],
+                answer: "Error: process not available".to_owned(),
+            }]
+        );
+        assert_eq!(contract.subprocesses[2].ids, [2, 3]);
+        let subprocess = |amplitude_type, incoming_pdg: [i64; 2]| Subprocess {
+            ids: vec![],
+            coupling_powers: IndexMap::new(),
+            options: IndexMap::new(),
+            amplitude_type,
+            incoming_pdg: incoming_pdg.to_vec(),
+            outgoing_pdg: vec![6, -6],
+        };
+        let order = [
+            subprocess(Loop, [21, 21]),
+            subprocess(Tree, [21, 21]),
+            subprocess(Loop, [21, 21]),
+        ];
+        assert_eq!(
+            contract.match_subprocesses(&order).unwrap(),
+            [vec![0], vec![1], vec![2, 3]]
+        );
+        assert_eq!(
+            contract.match_subprocesses(&order[..2]).unwrap(),
+            [vec![0], vec![1]]
+        );
+        assert!(matches!(
+            contract.match_subprocesses(&[subprocess(Loop, [2, -2])]),
+            Err(BLHAError::ContractError(_))
+        ));
+        assert!(matches!(
+            contract.match_subprocesses(&[subprocess(ccTree, [21, 21])]),
+            Err(BLHAError::ContractError(_))
+        ));
+    }
+
+    #[test]
+    fn coupling_power_matching_test() {
+        let content = r#"InterfaceVersion BLHA2 | OK
+AmplitudeType Loop | OK
+CouplingPower QCD 2 | OK
+CouplingPower QED 1 | OK
+2 -2 -> 6 -6 | 1 1
+CouplingPower QCD 3 | OK
+CouplingPower QED 0 | OK
+2 -2 -> 6 -6 | 1 2
+"#;
+        let contract = super::blha_contract::contract(content).unwrap().unwrap();
+        let subprocess = |coupling_powers: &[(&str, i64)]| Subprocess {
+            ids: vec![],
+            amplitude_type: Loop,
+            incoming_pdg: vec![2, -2],
+            outgoing_pdg: vec![6, -6],
+            coupling_powers: coupling_powers
+                .iter()
+                .map(|(coupling, power)| (coupling.to_string(), *power))
+                .collect(),
+            options: IndexMap::new(),
+        };
+        assert_eq!(
+            contract
+                .match_subprocesses(&[subprocess(&[("QCD", 3)]), subprocess(&[("QED", 1)])])
+                .unwrap(),
+            [vec![2], vec![1]]
+        );
+        // Without coupling powers, the subprocesses are matched in order
+        assert_eq!(
+            contract
+                .match_subprocesses(&[subprocess(&[]), subprocess(&[])])
+                .unwrap(),
+            [vec![1], vec![2]]
+        );
+        assert!(matches!(
+            contract.match_subprocesses(&[subprocess(&[("QCD", 4)])]),
+            Err(BLHAError::ContractError(_))
+        ));
+    }
+
+    #[test]
+    fn blha1_contract_test() {
+        let content = r#"# BLHA1 contract
+MatrixElementSquareType CHsummed | OK
+CorrectionType QCD | OK 
+IRregularisation CDR | OK
+AlphasPower 2 | OK
+AlphaPower 0 | OK
+21 21 -> 6 -6 | 1 1
+AlphasPower 3 | OK
+21 21 -> 6 -6 21 | 1 2
+"#;
+        let contract = super::blha_contract::contract(content).unwrap().unwrap();
+        assert_eq!(
+            contract.interface_version().unwrap(),
+            InterfaceVersion::BLHA1
+        );
+        assert_eq!(contract.subprocesses.len(), 2);
+        assert_eq!(
+            contract.subprocesses[0].coupling_powers,
+            IndexMap::from([("QCD".to_owned(), 2), ("QED".to_owned(), 0)])
+        );
+        assert_eq!(
+            contract.subprocesses[1].coupling_powers,
+            IndexMap::from([("QCD".to_owned(), 3), ("QED".to_owned(), 0)])
+        );
+        assert_eq!(contract.subprocesses[1].ids, [2]);
+    }
 }
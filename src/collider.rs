@@ -0,0 +1,252 @@
+use crate::{pdf::Pdf, util::FloatRandom};
+use num_traits::Float;
+use pyo3::prelude::*;
+use std::{path::PathBuf, sync::Arc};
+
+/// Beam of a collider, either a hadron described by a PDF set or an elementary particle entering the hard process
+#[pyclass]
+#[derive(Debug, Clone)]
+pub(crate) struct Beam {
+    #[pyo3(get)]
+    pub(crate) energy: f64,
+    pdf: Option<Arc<Pdf>>,
+    /// PDG id of the beam particle, if given
+    #[pyo3(get)]
+    pdg: Option<i64>,
+}
+
+impl Beam {
+    /// PDG id of the beam particle providing the incoming parton `parton`: the given id, a proton for hadron beams and
+    /// the parton itself for elementary beams
+    pub(crate) fn pdg_id(&self, parton: i64) -> i64 {
+        return match (self.pdg, &self.pdf) {
+            (Some(pdg), _) => pdg,
+            (None, Some(_)) => 2212,
+            (None, None) => parton,
+        };
+    }
+}
+
+#[pymethods]
+impl Beam {
+    #[new]
+    #[pyo3(signature = (energy, pdf = None, member = 0, pdg = None))]
+    fn new(energy: f64, pdf: Option<PathBuf>, member: usize, pdg: Option<i64>) -> PyResult<Self> {
+        let pdf = match pdf {
+            Some(path) => Some(Arc::new(Pdf::load(&path, member)?)),
+            None => None,
+        };
+        return Ok(Beam { energy, pdf, pdg });
+    }
+
+    /// Momentum density `x f(x, Q^2)` of parton `pid`, or `None` for a beam without PDF
+    fn xfx(&self, pid: i64, x: f64, q2: f64) -> Option<f64> {
+        return self.pdf.as_ref().map(|pdf| pdf.xfx(pid, x, q2));
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "Beam(energy={:e}, pdf={})",
+            self.energy,
+            if self.pdf.is_some() { "..." } else { "None" }
+        );
+    }
+}
+
+/// Momentum fractions of the incoming partons sampled by `Collider::sample`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Luminosity {
+    /// Partonic center of mass energy squared
+    pub(crate) s: f64,
+    /// Rapidity of the partonic center of mass frame in the lab frame
+    pub(crate) rapidity: f64,
+    /// Jacobian of the momentum fractions times the parton densities
+    pub(crate) weight: f64,
+}
+
+/// Collider with two beams colliding head-on along the z-axis, where `beam1` moves in positive z-direction
+#[pyclass]
+#[derive(Debug, Clone)]
+pub(crate) struct Collider {
+    #[pyo3(get)]
+    beam1: Beam,
+    #[pyo3(get)]
+    beam2: Beam,
+    #[pyo3(get)]
+    factorization_scale: Option<f64>,
+}
+
+impl Collider {
+    /// Number of random numbers consumed by `sample`
+    pub(crate) fn dimension(&self) -> usize {
+        return self.beams().iter().filter(|b| b.pdf.is_some()).count();
+    }
+
+    pub(crate) fn beams(&self) -> [&Beam; 2] {
+        return [&self.beam1, &self.beam2];
+    }
+
+    /// Sample the momentum fractions of partons `pdg` with partonic center of mass energy squared above `s_min`.
+    ///
+    /// For two hadronic beams, `tau = x_1 x_2` is sampled logarithmically and the rapidity `ln(x_1 / x_2) / 2`
+    /// uniformly, for a single hadronic beam the momentum fraction is sampled logarithmically. The PDFs are evaluated
+    /// at the factorization scale, which defaults to the partonic center of mass energy.
+    pub(crate) fn sample<F: Float>(
+        &self,
+        pdg: &[i64],
+        s_min: f64,
+        rng: &mut impl FloatRandom<F>,
+    ) -> Luminosity {
+        let mut u = || rng.generate().to_f64().unwrap();
+        let s_had = 4. * self.beam1.energy * self.beam2.energy;
+        let ln_tau_min = (s_min / s_had).min(1.).ln();
+        let mut x = [1.; 2];
+        let mut weight = 1.;
+        match (&self.beam1.pdf, &self.beam2.pdf) {
+            (Some(_), Some(_)) => {
+                let ln_tau = (1. - u()) * ln_tau_min;
+                let y = (u() - 0.5) * -ln_tau;
+                x = [(0.5 * ln_tau + y).exp(), (0.5 * ln_tau - y).exp()];
+                weight = ln_tau * ln_tau_min;
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                let i = if self.beam1.pdf.is_some() { 0 } else { 1 };
+                x[i] = ((1. - u()) * ln_tau_min).exp();
+                weight = -ln_tau_min;
+            }
+            (None, None) => (),
+        }
+        let s = x[0] * x[1] * s_had;
+        let q2 = self.factorization_scale.map_or(s, |mu| mu * mu);
+        for ((beam, x), pid) in self.beams().iter().zip(x.iter()).zip(pdg.iter()) {
+            if let Some(ref pdf) = beam.pdf {
+                // The Jacobian x of the logarithmic sampling cancels the 1/x of the density
+                weight *= pdf.xfx(*pid, *x, q2);
+            }
+        }
+        return Luminosity {
+            s,
+            rapidity: 0.5 * (x[0] * self.beam1.energy / (x[1] * self.beam2.energy)).ln(),
+            weight,
+        };
+    }
+}
+
+#[pymethods]
+impl Collider {
+    #[new]
+    #[pyo3(signature = (beam1, beam2, factorization_scale = None))]
+    fn new(beam1: Beam, beam2: Beam, factorization_scale: Option<f64>) -> Self {
+        return Collider {
+            beam1,
+            beam2,
+            factorization_scale,
+        };
+    }
+
+    /// Hadronic center of mass energy
+    #[getter]
+    fn sqrt_s(&self) -> f64 {
+        return 2. * (self.beam1.energy * self.beam2.energy).sqrt();
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "Collider(beam1={}, beam2={}, factorization_scale={:?})",
+            self.beam1.__repr__(),
+            self.beam2.__repr__(),
+            self.factorization_scale
+        );
+    }
+}
+
+/// Boost the momenta `vecs` along the z-axis by the rapidity `y`
+pub(crate) fn boost_z<F: Float>(vecs: &mut [[F; 4]], y: F) {
+    let (sinh, cosh) = (y.sinh(), y.cosh());
+    for p in vecs.iter_mut() {
+        let (e, z) = (p[0], p[3]);
+        p[0] = cosh * e + sinh * z;
+        p[3] = sinh * e + cosh * z;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrate::Accumulator;
+    use fastrand::Rng;
+
+    #[test]
+    fn luminosity_test() {
+        // Constant density f(x) = 1, i.e. x f(x) = x, on a grid fine enough in the relevant range
+        let x = (0..=200)
+            .map(|i| 10_f64.powf(-4. + i as f64 / 50.))
+            .collect::<Vec<_>>();
+        let mut content = format!(
+            "Format: lhagrid1\n---\n{}\n1 1000\n21\n",
+            x.iter()
+                .map(|x| format!("{x:e}"))
+                .collect::<Vec<_>>()
+                .join(" ")
+        );
+        for x in x.iter() {
+            content += &format!("{x:e}\n{x:e}\n");
+        }
+        content += "---\n";
+        let pdf = Arc::new(Pdf::parse(&content).unwrap());
+        let (e1, e2) = (4000., 1000.);
+        let hadron = |energy| Beam {
+            energy,
+            pdf: Some(pdf.clone()),
+            pdg: None,
+        };
+        let collider = Collider::new(hadron(e1), hadron(e2), None);
+        assert_eq!(collider.dimension(), 2);
+        assert_eq!(collider.beams().map(|beam| beam.pdg_id(21)), [2212, 2212]);
+        let s_had = collider.sqrt_s().powi(2);
+        let tau_min = 0.01;
+        let mut rng = Rng::with_seed(1);
+        let mut acc = Accumulator::default();
+        for _ in 0..100_000 {
+            let lumi = collider.sample(&[21, 21], tau_min * s_had, &mut rng);
+            assert!(lumi.s >= tau_min * s_had * (1. - 1E-12) && lumi.s <= s_had);
+            let x1 = (lumi.s / s_had).sqrt() * (lumi.rapidity - 0.5 * (e1 / e2).ln()).exp();
+            assert!(x1 <= 1. + 1E-12);
+            acc.add(lumi.weight);
+        }
+        // Area of 0 < x_1, x_2 < 1 with x_1 x_2 > tau_min
+        let area = 1. - tau_min + tau_min * tau_min.ln();
+        assert!((acc.mean() - area).abs() < 5. * acc.error());
+
+        let collider = Collider::new(
+            hadron(e1),
+            Beam {
+                energy: e2,
+                pdf: None,
+                pdg: None,
+            },
+            None,
+        );
+        assert_eq!(collider.dimension(), 1);
+        let mut acc = Accumulator::default();
+        for _ in 0..100_000 {
+            acc.add(collider.sample(&[21, 11], tau_min * s_had, &mut rng).weight);
+        }
+        assert!((acc.mean() - (1. - tau_min)).abs() < 5. * acc.error());
+    }
+
+    #[test]
+    fn boost_test() {
+        let mut vecs = [[5., 0., 0., 5.], [5., 0., 0., -5.], [10., 1., 2., 3.]];
+        let m_sq = |p: &[f64; 4]| p[0] * p[0] - p[1] * p[1] - p[2] * p[2] - p[3] * p[3];
+        let before = vecs.map(|p| m_sq(&p));
+        boost_z(&mut vecs, 0.7);
+        boost_z(&mut vecs[..1], -0.7);
+        assert!((vecs[0][0] - 5.).abs() < 1E-12 && (vecs[0][3] - 5.).abs() < 1E-12);
+        assert!((vecs[1][0] / vecs[1][3] + 1.).abs() < 1E-12);
+        for (p, m) in vecs.iter().zip(before.iter()) {
+            assert!((m_sq(p) - m).abs() < 1E-10);
+        }
+        assert_eq!(vecs[2][1..3], [1., 2.]);
+    }
+}
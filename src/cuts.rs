@@ -0,0 +1,209 @@
+use pyo3::prelude::*;
+
+use crate::{
+    kinematics::{delta_r, pt, rapidity},
+    util::scalar,
+};
+
+/// Generation-level cut on the final-state particles of a phase-space point. Particles are selected by their PDG id.
+#[pyclass]
+#[derive(Debug)]
+pub(crate) enum Cut {
+    /// `min <= pT <= max` for all selected particles
+    #[pyo3(constructor = (particles, min = 0.0, max = f64::INFINITY))]
+    PT {
+        particles: Vec<i64>,
+        min: f64,
+        max: f64,
+    },
+    /// `|y| <= max` for all selected particles
+    #[pyo3(constructor = (particles, max))]
+    Rapidity { particles: Vec<i64>, max: f64 },
+    /// `Delta R >= min` for all pairs of distinct particles from the two groups
+    #[pyo3(constructor = (particles1, particles2, min))]
+    DeltaR {
+        particles1: Vec<i64>,
+        particles2: Vec<i64>,
+        min: f64,
+    },
+    /// `min <= m_ij <= max` for all pairs of distinct particles from the two groups
+    #[pyo3(constructor = (particles1, particles2, min = 0.0, max = f64::INFINITY))]
+    InvariantMass {
+        particles1: Vec<i64>,
+        particles2: Vec<i64>,
+        min: f64,
+        max: f64,
+    },
+    /// User-defined cut, called with the list of all momenta and returning whether the point is accepted
+    #[pyo3(constructor = (function))]
+    Custom { function: PyObject },
+}
+
+impl Clone for Cut {
+    fn clone(&self) -> Self {
+        match self {
+            Cut::PT {
+                particles,
+                min,
+                max,
+            } => Cut::PT {
+                particles: particles.clone(),
+                min: *min,
+                max: *max,
+            },
+            Cut::Rapidity { particles, max } => Cut::Rapidity {
+                particles: particles.clone(),
+                max: *max,
+            },
+            Cut::DeltaR {
+                particles1,
+                particles2,
+                min,
+            } => Cut::DeltaR {
+                particles1: particles1.clone(),
+                particles2: particles2.clone(),
+                min: *min,
+            },
+            Cut::InvariantMass {
+                particles1,
+                particles2,
+                min,
+                max,
+            } => Cut::InvariantMass {
+                particles1: particles1.clone(),
+                particles2: particles2.clone(),
+                min: *min,
+                max: *max,
+            },
+            Cut::Custom { function } => Python::with_gil(|py| Cut::Custom {
+                function: function.clone_ref(py),
+            }),
+        }
+    }
+}
+
+/// Momenta of the outgoing particles of `vecs` whose PDG id is contained in `particles`, together with their index
+fn select<'a>(
+    vecs: &'a [[f64; 4]],
+    pdg: &'a [i64],
+    n_in: usize,
+    particles: &'a [i64],
+) -> impl Iterator<Item = (usize, &'a [f64; 4])> + 'a {
+    return vecs
+        .iter()
+        .zip(pdg.iter())
+        .enumerate()
+        .skip(n_in)
+        .filter(|(_, (_, id))| particles.contains(id))
+        .map(|(i, (p, _))| (i, p));
+}
+
+/// Check all pairs of distinct selected particles of the two groups with `f`
+fn all_pairs(
+    vecs: &[[f64; 4]],
+    pdg: &[i64],
+    n_in: usize,
+    particles: (&[i64], &[i64]),
+    f: impl Fn(&[f64; 4], &[f64; 4]) -> bool,
+) -> bool {
+    return select(vecs, pdg, n_in, particles.0)
+        .all(|(i, p)| select(vecs, pdg, n_in, particles.1).all(|(j, q)| i == j || f(p, q)));
+}
+
+impl Cut {
+    /// Check whether the point `vecs` of a subprocess with the PDG ids `pdg` of all legs and `n_in` incoming
+    /// particles passes the cut
+    pub(crate) fn passes(&self, vecs: &[[f64; 4]], pdg: &[i64], n_in: usize) -> PyResult<bool> {
+        let result = match self {
+            Cut::PT {
+                particles,
+                min,
+                max,
+            } => select(vecs, pdg, n_in, particles).all(|(_, p)| (*min..=*max).contains(&pt(p))),
+            Cut::Rapidity { particles, max } => {
+                select(vecs, pdg, n_in, particles).all(|(_, p)| rapidity(p).abs() <= *max)
+            }
+            Cut::DeltaR {
+                particles1,
+                particles2,
+                min,
+            } => all_pairs(vecs, pdg, n_in, (particles1, particles2), |p, q| {
+                delta_r(p, q) >= *min
+            }),
+            Cut::InvariantMass {
+                particles1,
+                particles2,
+                min,
+                max,
+            } => all_pairs(vecs, pdg, n_in, (particles1, particles2), |p, q| {
+                let pq = [p[0] + q[0], p[1] + q[1], p[2] + q[2], p[3] + q[3]];
+                (*min..=*max).contains(&scalar(&pq, &pq).max(0.).sqrt())
+            }),
+            Cut::Custom { function } => {
+                return Python::with_gil(|py| function.call1(py, (vecs.to_vec(),))?.extract(py));
+            }
+        };
+        return Ok(result);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cut_test() {
+        // u u~ -> g g u with the gluons at pT = 40 and 70 and the u quark at pT = 30
+        let pdg = [2, -2, 21, 21, 2];
+        let vecs = [
+            [100., 0., 0., 100.],
+            [100., 0., 0., -100.],
+            [50., 40., 0., 30.],
+            [70., -70., 0., 0.],
+            [30., 30., 0., 0.],
+        ];
+        let check = |cut: Cut| cut.passes(&vecs, &pdg, 2).unwrap();
+        let pt_cut = |min, max| Cut::PT {
+            particles: vec![21],
+            min,
+            max,
+        };
+        assert!(check(pt_cut(35., f64::INFINITY)));
+        assert!(!check(pt_cut(45., f64::INFINITY)));
+        assert!(!check(pt_cut(0., 60.)));
+        // The incoming quarks are never selected
+        assert!(check(Cut::PT {
+            particles: vec![2, -2],
+            min: 25.,
+            max: 35.
+        }));
+        assert!(check(Cut::Rapidity {
+            particles: vec![21],
+            max: 0.7
+        }));
+        assert!(!check(Cut::Rapidity {
+            particles: vec![21],
+            max: 0.6
+        }));
+        // Delta R is 0.69 between the first gluon and the u quark, pi between the second gluon and the u quark and
+        // sqrt(0.69^2 + pi^2) between the gluons
+        let delta_r_cut = |particles2, min| Cut::DeltaR {
+            particles1: vec![21],
+            particles2,
+            min,
+        };
+        assert!(check(delta_r_cut(vec![21, 2], 0.4)));
+        assert!(!check(delta_r_cut(vec![2], 3.)));
+        assert!(check(delta_r_cut(vec![21], 3.2)));
+        // m(u, g_1)^2 = 80^2 - 70^2 - 30^2 = 600 and m(u, g_2)^2 = 100^2 - 40^2 = 8400
+        let mass_cut = |min, max| Cut::InvariantMass {
+            particles1: vec![2],
+            particles2: vec![21],
+            min,
+            max,
+        };
+        assert!(check(mass_cut(20., 100.)));
+        assert!(!check(mass_cut(25., f64::INFINITY)));
+        assert!(!check(mass_cut(0., 90.)));
+    }
+}
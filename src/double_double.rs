@@ -0,0 +1,790 @@
+use crate::util::FloatRandom;
+use num_traits::{Float, FloatConst, Num, NumCast, One, ToPrimitive, Zero};
+use std::cmp::Ordering;
+use std::f64::consts;
+use std::num::FpCategory;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// Double-double number `hi + lo` with `|lo| <= ulp(hi) / 2`, giving about 32 significant digits with `f64`
+/// arithmetic (Hida, Li, Bailey, 10.1109/ARITH.2001.930115)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct DoubleDouble {
+    hi: f64,
+    lo: f64,
+}
+
+/// Machine epsilon of the double-double representation, `2^-104`
+const EPSILON: f64 = 4.930380657631324e-32;
+/// Maximal order of the Taylor series
+const MAX_TAYLOR_ORDER: usize = 60;
+
+/// Exact sum `a + b = s + e`
+#[inline]
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    let bb = s - a;
+    return (s, (a - (s - bb)) + (b - bb));
+}
+
+/// Exact sum `a + b = s + e`, assuming `|a| >= |b|`
+#[inline]
+fn quick_two_sum(a: f64, b: f64) -> (f64, f64) {
+    let s = a + b;
+    return (s, b - (s - a));
+}
+
+/// Exact product `a * b = p + e`
+#[inline]
+fn two_prod(a: f64, b: f64) -> (f64, f64) {
+    let p = a * b;
+    return (p, a.mul_add(b, -p));
+}
+
+impl DoubleDouble {
+    pub(crate) const fn new(hi: f64, lo: f64) -> Self {
+        return DoubleDouble { hi, lo };
+    }
+
+    pub(crate) const fn from_f64(x: f64) -> Self {
+        return DoubleDouble { hi: x, lo: 0. };
+    }
+
+    #[inline]
+    fn normalized(hi: f64, lo: f64) -> Self {
+        let (hi, lo) = quick_two_sum(hi, lo);
+        return DoubleDouble { hi, lo };
+    }
+
+    /// Multiply by the power of two `2^n`, which is exact
+    #[inline]
+    fn ldexp(self, n: i32) -> Self {
+        let scale = 2_f64.powi(n);
+        return DoubleDouble::new(self.hi * scale, self.lo * scale);
+    }
+
+    #[inline]
+    fn sqr(self) -> Self {
+        return self * self;
+    }
+
+    /// `exp(x) - 1` for `|x| <= ln(2) / 2`
+    fn reduced_exp_m1(self) -> Self {
+        // Reduce the argument further by 2^10 and undo it with `(1 + s)^2 - 1 = s (s + 2)`
+        const K: i32 = 10;
+        let r = self.ldexp(-K);
+        let mut inv_factorial = DoubleDouble::one();
+        let mut s = DoubleDouble::zero();
+        let mut power = r;
+        for n in 1..MAX_TAYLOR_ORDER {
+            inv_factorial = inv_factorial / DoubleDouble::from_f64(n as f64);
+            let term = power * inv_factorial;
+            s = s + term;
+            if term.hi.abs() <= EPSILON * s.hi.abs() {
+                break;
+            }
+            power = power * r;
+        }
+        for _ in 0..K {
+            s = s * (s + DoubleDouble::from_f64(2.));
+        }
+        return s;
+    }
+
+    /// Sine and cosine for `|x| <= pi / 4`
+    fn reduced_sin_cos(self) -> (Self, Self) {
+        let x_sq = self.sqr();
+        let mut sin = self;
+        let mut cos = DoubleDouble::one();
+        let mut term = self;
+        for n in 1..MAX_TAYLOR_ORDER / 2 {
+            let k = (2 * n) as f64;
+            term = -term * x_sq / DoubleDouble::from_f64(k * (k + 1.));
+            sin = sin + term;
+            if term.hi.abs() <= EPSILON * sin.hi.abs() {
+                break;
+            }
+        }
+        term = DoubleDouble::one();
+        for n in 1..MAX_TAYLOR_ORDER / 2 {
+            let k = (2 * n) as f64;
+            term = -term * x_sq / DoubleDouble::from_f64(k * (k - 1.));
+            cos = cos + term;
+            if term.hi.abs() <= EPSILON {
+                break;
+            }
+        }
+        return (sin, cos);
+    }
+}
+
+impl PartialOrd for DoubleDouble {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return match self.hi.partial_cmp(&other.hi) {
+            Some(Ordering::Equal) => self.lo.partial_cmp(&other.lo),
+            ord => ord,
+        };
+    }
+}
+
+impl Neg for DoubleDouble {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        return DoubleDouble::new(-self.hi, -self.lo);
+    }
+}
+
+impl Add for DoubleDouble {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, other: Self) -> Self {
+        let (s, e) = two_sum(self.hi, other.hi);
+        let (t, f) = two_sum(self.lo, other.lo);
+        let (s, e) = quick_two_sum(s, e + t);
+        return DoubleDouble::normalized(s, e + f);
+    }
+}
+
+impl Sub for DoubleDouble {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        return self + -other;
+    }
+}
+
+impl Mul for DoubleDouble {
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, other: Self) -> Self {
+        let (p, e) = two_prod(self.hi, other.hi);
+        return DoubleDouble::normalized(p, e + (self.hi * other.lo + self.lo * other.hi));
+    }
+}
+
+impl Div for DoubleDouble {
+    type Output = Self;
+
+    #[inline]
+    fn div(self, other: Self) -> Self {
+        let q1 = self.hi / other.hi;
+        let r = self - other * DoubleDouble::from_f64(q1);
+        let q2 = r.hi / other.hi;
+        let r = r - other * DoubleDouble::from_f64(q2);
+        let q3 = r.hi / other.hi;
+        return DoubleDouble::normalized(q1, q2) + DoubleDouble::from_f64(q3);
+    }
+}
+
+impl Rem for DoubleDouble {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self {
+        return self - (self / other).trunc() * other;
+    }
+}
+
+impl Zero for DoubleDouble {
+    fn zero() -> Self {
+        return DoubleDouble::new(0., 0.);
+    }
+
+    fn is_zero(&self) -> bool {
+        return self.hi == 0.;
+    }
+}
+
+impl One for DoubleDouble {
+    fn one() -> Self {
+        return DoubleDouble::new(1., 0.);
+    }
+}
+
+impl Num for DoubleDouble {
+    type FromStrRadixErr = <f64 as Num>::FromStrRadixErr;
+
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        return f64::from_str_radix(str, radix).map(DoubleDouble::from_f64);
+    }
+}
+
+impl ToPrimitive for DoubleDouble {
+    fn to_i64(&self) -> Option<i64> {
+        let t = self.trunc();
+        return (t.hi as i128 + t.lo as i128).to_i64();
+    }
+
+    fn to_u64(&self) -> Option<u64> {
+        let t = self.trunc();
+        return (t.hi as i128 + t.lo as i128).to_u64();
+    }
+
+    fn to_f64(&self) -> Option<f64> {
+        return Some(self.hi + self.lo);
+    }
+}
+
+impl NumCast for DoubleDouble {
+    fn from<T: ToPrimitive>(n: T) -> Option<Self> {
+        let hi = n.to_f64()?;
+        // Integers beyond 2^53 keep their remainder in the trailing part
+        if let Some(n) = n.to_i64()
+            && hi.trunc() == hi
+        {
+            return Some(DoubleDouble::new(hi, (n - hi as i64) as f64));
+        }
+        return Some(DoubleDouble::from_f64(hi));
+    }
+}
+
+impl FloatConst for DoubleDouble {
+    fn E() -> Self {
+        return DoubleDouble::new(consts::E, 1.4456468917292502e-16);
+    }
+
+    fn FRAC_1_PI() -> Self {
+        return Self::PI().recip();
+    }
+
+    fn FRAC_1_SQRT_2() -> Self {
+        return Self::SQRT_2().ldexp(-1);
+    }
+
+    fn FRAC_2_PI() -> Self {
+        return Self::FRAC_1_PI().ldexp(1);
+    }
+
+    fn FRAC_2_SQRT_PI() -> Self {
+        return Self::PI().sqrt().recip().ldexp(1);
+    }
+
+    fn FRAC_PI_2() -> Self {
+        return Self::PI().ldexp(-1);
+    }
+
+    fn FRAC_PI_3() -> Self {
+        return Self::PI() / DoubleDouble::from_f64(3.);
+    }
+
+    fn FRAC_PI_4() -> Self {
+        return Self::PI().ldexp(-2);
+    }
+
+    fn FRAC_PI_6() -> Self {
+        return Self::PI() / DoubleDouble::from_f64(6.);
+    }
+
+    fn FRAC_PI_8() -> Self {
+        return Self::PI().ldexp(-3);
+    }
+
+    fn LN_10() -> Self {
+        return DoubleDouble::new(consts::LN_10, -2.1707562233822494e-16);
+    }
+
+    fn LN_2() -> Self {
+        return DoubleDouble::new(consts::LN_2, 2.3190468138462996e-17);
+    }
+
+    fn LOG10_E() -> Self {
+        return DoubleDouble::new(consts::LOG10_E, 1.098319650216765e-17);
+    }
+
+    fn LOG2_E() -> Self {
+        return DoubleDouble::new(consts::LOG2_E, 2.0355273740931033e-17);
+    }
+
+    fn PI() -> Self {
+        return DoubleDouble::new(consts::PI, 1.2246467991473532e-16);
+    }
+
+    fn SQRT_2() -> Self {
+        return DoubleDouble::new(consts::SQRT_2, -9.667293313452913e-17);
+    }
+
+    fn TAU() -> Self {
+        return Self::PI().ldexp(1);
+    }
+}
+
+impl Float for DoubleDouble {
+    fn nan() -> Self {
+        return DoubleDouble::from_f64(f64::NAN);
+    }
+
+    fn infinity() -> Self {
+        return DoubleDouble::from_f64(f64::INFINITY);
+    }
+
+    fn neg_infinity() -> Self {
+        return DoubleDouble::from_f64(f64::NEG_INFINITY);
+    }
+
+    fn neg_zero() -> Self {
+        return DoubleDouble::from_f64(-0.);
+    }
+
+    fn min_value() -> Self {
+        return DoubleDouble::from_f64(f64::MIN);
+    }
+
+    fn min_positive_value() -> Self {
+        return DoubleDouble::from_f64(f64::MIN_POSITIVE);
+    }
+
+    fn max_value() -> Self {
+        return DoubleDouble::from_f64(f64::MAX);
+    }
+
+    fn epsilon() -> Self {
+        return DoubleDouble::from_f64(EPSILON);
+    }
+
+    fn is_nan(self) -> bool {
+        return self.hi.is_nan() || self.lo.is_nan();
+    }
+
+    fn is_infinite(self) -> bool {
+        return self.hi.is_infinite();
+    }
+
+    fn is_finite(self) -> bool {
+        return self.hi.is_finite();
+    }
+
+    fn is_normal(self) -> bool {
+        return self.hi.is_normal();
+    }
+
+    fn classify(self) -> FpCategory {
+        return self.hi.classify();
+    }
+
+    fn floor(self) -> Self {
+        let hi = self.hi.floor();
+        if hi == self.hi {
+            return DoubleDouble::normalized(hi, self.lo.floor());
+        }
+        return DoubleDouble::from_f64(hi);
+    }
+
+    fn ceil(self) -> Self {
+        let hi = self.hi.ceil();
+        if hi == self.hi {
+            return DoubleDouble::normalized(hi, self.lo.ceil());
+        }
+        return DoubleDouble::from_f64(hi);
+    }
+
+    fn round(self) -> Self {
+        let hi = self.hi.round();
+        if hi == self.hi {
+            return DoubleDouble::normalized(hi, self.lo.round());
+        }
+        if (hi - self.hi).abs() == 0.5 && self.lo != 0. {
+            // Halfway case of the leading part decided by the sign of the trailing part
+            return DoubleDouble::from_f64(if self.lo > 0. {
+                self.hi.ceil()
+            } else {
+                self.hi.floor()
+            });
+        }
+        return DoubleDouble::from_f64(hi);
+    }
+
+    fn trunc(self) -> Self {
+        return if self.hi >= 0. {
+            self.floor()
+        } else {
+            self.ceil()
+        };
+    }
+
+    fn fract(self) -> Self {
+        return self - self.trunc();
+    }
+
+    fn abs(self) -> Self {
+        return if self.hi < 0. { -self } else { self };
+    }
+
+    fn signum(self) -> Self {
+        return DoubleDouble::from_f64(self.hi.signum());
+    }
+
+    fn is_sign_positive(self) -> bool {
+        return self.hi.is_sign_positive();
+    }
+
+    fn is_sign_negative(self) -> bool {
+        return self.hi.is_sign_negative();
+    }
+
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        return self * a + b;
+    }
+
+    fn recip(self) -> Self {
+        return DoubleDouble::one() / self;
+    }
+
+    fn powi(self, n: i32) -> Self {
+        let mut base = self;
+        let mut res = DoubleDouble::one();
+        let mut k = n.unsigned_abs();
+        while k > 0 {
+            if k & 1 == 1 {
+                res = res * base;
+            }
+            base = base.sqr();
+            k >>= 1;
+        }
+        return if n < 0 { res.recip() } else { res };
+    }
+
+    fn powf(self, n: Self) -> Self {
+        return (n * self.ln()).exp();
+    }
+
+    fn sqrt(self) -> Self {
+        if self.hi <= 0. {
+            return DoubleDouble::from_f64(self.hi.sqrt());
+        }
+        // One Newton step starting from the f64 result doubles the number of correct digits
+        let x = self.hi.sqrt();
+        let r = self - DoubleDouble::from_f64(x).sqr();
+        return DoubleDouble::from_f64(x) + DoubleDouble::from_f64(r.hi / (2. * x));
+    }
+
+    fn exp(self) -> Self {
+        if self.hi > 709.8 {
+            return DoubleDouble::infinity();
+        }
+        if self.hi < -745.2 {
+            return DoubleDouble::zero();
+        }
+        let k = (self.hi / consts::LN_2).round();
+        let r = self - Self::LN_2() * DoubleDouble::from_f64(k);
+        return (r.reduced_exp_m1() + DoubleDouble::one()).ldexp(k as i32);
+    }
+
+    fn exp2(self) -> Self {
+        return (self * Self::LN_2()).exp();
+    }
+
+    fn ln(self) -> Self {
+        if self.hi <= 0. || !self.is_finite() {
+            return DoubleDouble::from_f64(self.hi.ln());
+        }
+        // One Newton step `x + a exp(-x) - 1` starting from the f64 result
+        let x = DoubleDouble::from_f64(self.hi.ln());
+        return x + self * (-x).exp() - DoubleDouble::one();
+    }
+
+    fn log(self, base: Self) -> Self {
+        return self.ln() / base.ln();
+    }
+
+    fn log2(self) -> Self {
+        return self.ln() * Self::LOG2_E();
+    }
+
+    fn log10(self) -> Self {
+        return self.ln() * Self::LOG10_E();
+    }
+
+    fn max(self, other: Self) -> Self {
+        return if self.is_nan() || other > self {
+            other
+        } else {
+            self
+        };
+    }
+
+    fn min(self, other: Self) -> Self {
+        return if self.is_nan() || other < self {
+            other
+        } else {
+            self
+        };
+    }
+
+    fn abs_sub(self, other: Self) -> Self {
+        return (self - other).max(DoubleDouble::zero());
+    }
+
+    fn cbrt(self) -> Self {
+        if self.is_zero() || !self.is_finite() {
+            return self;
+        }
+        let y = DoubleDouble::from_f64(self.hi.cbrt());
+        let three = DoubleDouble::from_f64(3.);
+        return y - (y.powi(3) - self) / (three * y.sqr());
+    }
+
+    fn hypot(self, other: Self) -> Self {
+        return (self.sqr() + other.sqr()).sqrt();
+    }
+
+    fn sin(self) -> Self {
+        return self.sin_cos().0;
+    }
+
+    fn cos(self) -> Self {
+        return self.sin_cos().1;
+    }
+
+    fn tan(self) -> Self {
+        let (sin, cos) = self.sin_cos();
+        return sin / cos;
+    }
+
+    fn asin(self) -> Self {
+        return self.atan2((DoubleDouble::one() - self.sqr()).sqrt());
+    }
+
+    fn acos(self) -> Self {
+        return (DoubleDouble::one() - self.sqr()).sqrt().atan2(self);
+    }
+
+    fn atan(self) -> Self {
+        return self.atan2(DoubleDouble::one());
+    }
+
+    fn atan2(self, other: Self) -> Self {
+        if self.is_zero() && other.is_zero() {
+            return DoubleDouble::from_f64(self.hi.atan2(other.hi));
+        }
+        // One Newton step on the angle of the normalized vector starting from the f64 result
+        let z = DoubleDouble::from_f64(self.hi.atan2(other.hi));
+        let r = self.hypot(other);
+        let (x, y) = (other / r, self / r);
+        let (sin, cos) = z.sin_cos();
+        return if x.hi.abs() > y.hi.abs() {
+            z + (y - sin) / cos
+        } else {
+            z - (x - cos) / sin
+        };
+    }
+
+    fn sin_cos(self) -> (Self, Self) {
+        if !self.is_finite() {
+            return (DoubleDouble::nan(), DoubleDouble::nan());
+        }
+        let t = self - Self::TAU() * (self / Self::TAU()).round();
+        let j = (t / Self::FRAC_PI_2()).round();
+        let (sin, cos) = (t - Self::FRAC_PI_2() * j).reduced_sin_cos();
+        return match j.hi as i64 {
+            0 => (sin, cos),
+            1 => (cos, -sin),
+            -1 => (-cos, sin),
+            _ => (-sin, -cos),
+        };
+    }
+
+    fn exp_m1(self) -> Self {
+        if self.hi.abs() <= 0.5 * consts::LN_2 {
+            return self.reduced_exp_m1();
+        }
+        return self.exp() - DoubleDouble::one();
+    }
+
+    fn ln_1p(self) -> Self {
+        let u = DoubleDouble::one() + self;
+        if u == DoubleDouble::one() {
+            return self;
+        }
+        return u.ln() * self / (u - DoubleDouble::one());
+    }
+
+    fn sinh(self) -> Self {
+        if self.hi.abs() <= 0.5 {
+            // Avoid the cancellation in (exp(x) - exp(-x)) / 2
+            let e = self.exp_m1();
+            return (e + e / (e + DoubleDouble::one())).ldexp(-1);
+        }
+        let e = self.exp();
+        return (e - e.recip()).ldexp(-1);
+    }
+
+    fn cosh(self) -> Self {
+        let e = self.exp();
+        return (e + e.recip()).ldexp(-1);
+    }
+
+    fn tanh(self) -> Self {
+        if self.hi.abs() > 40. {
+            return self.signum();
+        }
+        return self.sinh() / self.cosh();
+    }
+
+    fn asinh(self) -> Self {
+        let x = self.abs();
+        let res = (x + (x.sqr() + DoubleDouble::one()).sqrt()).ln();
+        return if self.hi < 0. { -res } else { res };
+    }
+
+    fn acosh(self) -> Self {
+        return (self + (self.sqr() - DoubleDouble::one()).sqrt()).ln();
+    }
+
+    fn atanh(self) -> Self {
+        let one = DoubleDouble::one();
+        return ((one + self) / (one - self)).ln().ldexp(-1);
+    }
+
+    fn integer_decode(self) -> (u64, i16, i8) {
+        return self.hi.integer_decode();
+    }
+}
+
+/// Source of double-double random numbers drawing `f64` random numbers from `R`, such that the same random stream
+/// generates the same phase-space points in both precisions up to rounding
+pub(crate) struct DoubleDoubleRandom<'a, R>(pub(crate) &'a mut R);
+
+impl<R: FloatRandom<f64>> FloatRandom<DoubleDouble> for DoubleDoubleRandom<'_, R> {
+    #[inline]
+    fn generate(&mut self) -> DoubleDouble {
+        return DoubleDouble::from_f64(self.0.generate());
+    }
+
+    #[inline]
+    fn range(&mut self, min: DoubleDouble, max: DoubleDouble) -> DoubleDouble {
+        return min + self.generate() * (max - min);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rambo::{Scale, rambo};
+    use crate::util::substream;
+
+    fn assert_close(a: DoubleDouble, b: DoubleDouble, tol: f64) {
+        let diff = (a - b).abs().to_f64().unwrap();
+        assert!(
+            diff <= tol * b.abs().to_f64().unwrap().max(1.),
+            "{a:?} != {b:?}"
+        );
+    }
+
+    #[test]
+    fn arithmetic_test() {
+        let one = DoubleDouble::one();
+        let three = DoubleDouble::from_f64(3.);
+        let third = one / three;
+        assert!(third.lo != 0.);
+        assert_close(third * three, one, 1E-31);
+        assert_close(
+            DoubleDouble::from_f64(2.).sqrt().sqr(),
+            DoubleDouble::from_f64(2.),
+            1E-31,
+        );
+        assert_close(
+            DoubleDouble::from_f64(2.).sqrt(),
+            DoubleDouble::SQRT_2(),
+            1E-31,
+        );
+        // 1 + 1E-20 is not representable in f64
+        let x = one + DoubleDouble::from_f64(1E-20);
+        assert_eq!((x - one).to_f64(), Some(1E-20));
+        assert_eq!(
+            DoubleDouble::from_f64(-2.5).trunc(),
+            DoubleDouble::from_f64(-2.)
+        );
+        assert_eq!(
+            DoubleDouble::from_f64(2.5).round(),
+            DoubleDouble::from_f64(3.)
+        );
+        assert_close(
+            DoubleDouble::from_f64(7.) % DoubleDouble::from_f64(2.),
+            one,
+            1E-31,
+        );
+        assert_eq!(
+            <DoubleDouble as NumCast>::from(5).unwrap(),
+            DoubleDouble::from_f64(5.)
+        );
+    }
+
+    #[test]
+    fn functions_test() {
+        let one = DoubleDouble::one();
+        for x in [0.3, 1., 2.5, 12.3, -7.1] {
+            let x = DoubleDouble::from_f64(x) + DoubleDouble::from_f64(1E-20);
+            assert_close(x.exp().ln(), x, 1E-30);
+            let (sin, cos) = x.sin_cos();
+            assert_close(sin.sqr() + cos.sqr(), one, 1E-30);
+            assert_close(
+                sin.atan2(cos),
+                x - DoubleDouble::TAU() * (x / DoubleDouble::TAU()).round(),
+                1E-30,
+            );
+            assert_close(x.sinh().asinh(), x, 1E-29);
+            assert_close(x.cbrt().powi(3), x, 1E-30);
+        }
+        assert_close(DoubleDouble::one().exp(), DoubleDouble::E(), 1E-31);
+        assert_close(DoubleDouble::from_f64(2.).ln(), DoubleDouble::LN_2(), 1E-31);
+        assert_close(
+            DoubleDouble::from_f64(10.).ln(),
+            DoubleDouble::LN_10(),
+            1E-31,
+        );
+        assert_close(
+            DoubleDouble::one().atan().ldexp(2),
+            DoubleDouble::PI(),
+            1E-31,
+        );
+        assert_close(
+            DoubleDouble::FRAC_PI_6().sin(),
+            DoubleDouble::from_f64(0.5),
+            1E-31,
+        );
+        let small = DoubleDouble::from_f64(1E-10);
+        let sixth = small.sqr() / DoubleDouble::from_f64(6.);
+        assert_close(small.exp_m1() / small, one + small.ldexp(-1) + sixth, 1E-30);
+        assert_close(small.sinh() / small, one + sixth, 1E-30);
+    }
+
+    #[test]
+    fn rambo_double_double_test() {
+        let masses = [0., 0., 173., 173., 125.];
+        let masses_dd = masses.map(DoubleDouble::from_f64);
+        let s = 1E6;
+        let mut rng = substream(1, 0);
+        let mut rng_dd = substream(1, 0);
+        for _ in 0..100 {
+            let (_, vecs, _) = rambo(Scale::Fixed(s), &masses, 2, &mut rng);
+            let (_, vecs_dd, _) = rambo(
+                Scale::Fixed(DoubleDouble::from_f64(s)),
+                &masses_dd,
+                2,
+                &mut DoubleDoubleRandom(&mut rng_dd),
+            );
+            for i in 0..=3 {
+                let total = vecs_dd[..2]
+                    .iter()
+                    .fold(DoubleDouble::zero(), |a, p| a + p[i])
+                    - vecs_dd[2..]
+                        .iter()
+                        .fold(DoubleDouble::zero(), |a, p| a + p[i]);
+                assert!(total.abs().to_f64().unwrap() < 1E-26);
+            }
+            for (p, m) in vecs_dd.iter().zip(masses_dd.iter()) {
+                let p_sq = p[0].sqr() - p[1].sqr() - p[2].sqr() - p[3].sqr();
+                assert!((p_sq - m.sqr()).abs().to_f64().unwrap() < 1E-24);
+            }
+            // Same random numbers yield the same point up to the f64 precision
+            for (p, p_dd) in vecs.iter().zip(vecs_dd.iter()) {
+                for i in 0..=3 {
+                    assert!((p[i] - p_dd[i].to_f64().unwrap()).abs() < 1E-9);
+                }
+            }
+        }
+    }
+}
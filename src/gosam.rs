@@ -1,20 +1,106 @@
 use crate::{
-    blha::{AmplitudeType, OneLoopProvider, Order, Subprocess, error::BLHAError},
+    blha::{
+        AmplitudeType, InterfaceVersion, OLP, OneLoopProvider, Order, Subprocess, TreeLayout,
+        error::BLHAError,
+    },
+    collider::{Collider, boost_z},
+    cuts::Cut,
+    double_double::{DoubleDouble, DoubleDoubleRandom},
+    integrate::{Accumulator, Component, IntegrationResult},
+    kinematics::{Frame, invariants, momentum_violation, project},
+    lhe::{EventScales, EventWeights, LHEInit, LHEWriter, colour_flow},
     model::Model,
-    rambo::{Scale, rambo},
+    rambo::{Hypercube, Scale, dimension, rambo},
+    rescue::{Rescue, rescue},
+    result::{EvalResult, TypedResult},
+    unweight::{UnweightedEvents, Unweighter},
+    util::{FloatRandom, scalar, substream},
+    validate::{
+        ColouredLeg, PoleCheck, ValidationResult, agreement, casimir_correlator, digits, ir_poles,
+        random_boost, random_rotation, suppression,
+    },
+    vegas::{VegasAccumulator, VegasGrid},
 };
 use indexmap::IndexMap;
+use num_traits::{Float, FloatConst};
+use numpy::{PyArray1, PyArray2, PyArray3, PyArrayMethods, PyReadonlyArray1, PyReadonlyArray3};
 use pyo3::types::IntoPyDict;
 use std::hash::{DefaultHasher, Hash};
 use std::io::Write;
-use std::{hash::Hasher, path::PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::{
+    hash::Hasher,
+    path::{Path, PathBuf},
+};
 use thiserror::Error;
 
 use pyo3::{
-    exceptions::{PyIOError, PySyntaxError},
+    exceptions::{PyIOError, PyIndexError, PySyntaxError, PyValueError},
     prelude::*,
 };
 
+/// Number of points generated from a single RNG substream
+const CHUNK_SIZE: usize = 1000;
+/// Number of points evaluated between two convergence checks of `integrate`
+const INTEGRATION_BATCH_SIZE: usize = 10 * CHUNK_SIZE;
+/// Maximal number of points generated by `eval_random` to find a point passing the cuts
+const MAX_CUT_TRIES: usize = 1_000_000;
+
+/// Callback of `sample_with`, receiving the momenta, the phase-space weight and the outcome of each point
+type PointCallback<'a> = dyn FnMut(&[[f64; 4]], f64, Sampled) + 'a;
+
+/// Momenta, typed result and phase-space weight of a point returned by `sample` and `sample_decay`
+type SamplePoint = (Vec<[f64; 4]>, TypedResult, f64);
+
+/// Results and accuracies returned by `eval_batch`
+type BatchArrays<'py> = (Bound<'py, PyArray2<f64>>, Bound<'py, PyArray1<f64>>);
+
+/// Momenta, results, accuracies and phase-space weights returned by `sample_array`
+type SampleArrays<'py> = (
+    Bound<'py, PyArray3<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray1<f64>>,
+    Bound<'py, PyArray1<f64>>,
+);
+
+/// Conservation and on-shell violations of each point returned by `momentum_violation`
+type ViolationArrays<'py> = (Bound<'py, PyArray1<f64>>, Bound<'py, PyArray1<f64>>);
+
+/// Momenta, phase-space weights and conservation and on-shell violations returned by `sample_points`
+type PointArrays<'py> = (
+    Bound<'py, PyArray3<f64>>,
+    Bound<'py, PyArray1<f64>>,
+    Bound<'py, PyArray1<f64>>,
+    Bound<'py, PyArray1<f64>>,
+);
+
+/// Partonic energy, momenta, phase-space weight and the conservation and on-shell violations of a generated point,
+/// where the violations are computed in the precision of the generation before the point is rounded to `f64`
+type GeneratedPoint = (f64, Vec<[f64; 4]>, f64, [f64; 2]);
+
+/// Incoming and outgoing particles, amplitude type and OLP ids of a subprocess of a contract
+type ContractSubprocess = (Vec<i64>, Vec<i64>, AmplitudeType, Vec<i64>);
+
+/// Outcome of a point sampled by `sample_with`
+enum Sampled {
+    /// The point failed the cuts and was not evaluated
+    Cut,
+    /// The point was still unstable after all rescue steps and is discarded
+    Discarded(EvalResult),
+    /// The point was evaluated and is kept
+    Evaluated(EvalResult),
+}
+
+/// Settings shared by all methods sampling RAMBO points
+struct SampleSettings<'a> {
+    s: &'a PyScale,
+    frame: Option<&'a Frame>,
+    scale: Option<f64>,
+    seed: Option<u64>,
+    n_threads: Option<usize>,
+    progress: bool,
+}
+
 #[derive(Error, Debug)]
 enum GoSamError {
     #[error("Error while generating the process library: {0}")]
@@ -23,6 +109,24 @@ enum GoSamError {
     UnintializedError(String),
     #[error("IOError: {0}")]
     IOError(#[from] std::io::Error),
+    #[error("Invalid VEGAS grid: {0}")]
+    GridError(String),
+    #[error("Invalid integrand: {0}")]
+    IntegrandError(String),
+    #[error("Invalid scale: {0}")]
+    ScaleError(String),
+    #[error("Invalid frame: {0}")]
+    FrameError(String),
+    #[error("Invalid point: {0}")]
+    PointError(String),
+    #[error("Validation not possible: {0}")]
+    ValidationError(String),
+    #[error("Invalid array shape: {0}")]
+    ShapeError(String),
+    #[error("Invalid process: {0}")]
+    ProcessError(String),
+    #[error("{0}")]
+    IndexError(String),
     #[error(transparent)]
     BLHAError(#[from] BLHAError),
 }
@@ -32,8 +136,20 @@ impl From<GoSamError> for PyErr {
         match err {
             GoSamError::GenError(_) => PySyntaxError::new_err(err.to_string()),
             GoSamError::UnintializedError(_) => PySyntaxError::new_err(err.to_string()),
+            GoSamError::BLHAError(BLHAError::ParameterError(_)) => {
+                PyValueError::new_err(err.to_string())
+            }
             GoSamError::BLHAError(_) => PySyntaxError::new_err(err.to_string()),
             GoSamError::IOError(_) => PyIOError::new_err(err.to_string()),
+            GoSamError::ShapeError(_) => PyValueError::new_err(err.to_string()),
+            GoSamError::ProcessError(_) => PyValueError::new_err(err.to_string()),
+            GoSamError::IndexError(_) => PyIndexError::new_err(err.to_string()),
+            GoSamError::ScaleError(_) => PyValueError::new_err(err.to_string()),
+            GoSamError::FrameError(_) => PyValueError::new_err(err.to_string()),
+            GoSamError::PointError(_) => PyValueError::new_err(err.to_string()),
+            GoSamError::ValidationError(_) => PyValueError::new_err(err.to_string()),
+            GoSamError::IntegrandError(_) => PyValueError::new_err(err.to_string()),
+            GoSamError::GridError(_) => PyValueError::new_err(err.to_string()),
         }
     }
 }
@@ -46,10 +162,20 @@ impl From<BLHAError> for PyErr {
             BLHAError::ContractError(_) => PySyntaxError::new_err(err.to_string()),
             BLHAError::ParseError(_, _) => PySyntaxError::new_err(err.to_string()),
             BLHAError::LibraryError(_) => PyIOError::new_err(err.to_string()),
+            BLHAError::FunctionError(_) => PyIOError::new_err(err.to_string()),
+            BLHAError::ParameterError(_) => PyValueError::new_err(err.to_string()),
+            BLHAError::OrderError(_) => PyValueError::new_err(err.to_string()),
         }
     }
 }
 
+/// Center of mass energy squared passed from Python, either a fixed value or a `Scale`
+#[derive(FromPyObject)]
+pub(crate) enum ScaleArg {
+    Fixed(f64),
+    Scale(PyScale),
+}
+
 #[pyclass]
 #[pyo3(name = "Scale")]
 #[derive(Clone)]
@@ -57,56 +183,834 @@ pub(crate) enum PyScale {
     Fixed(f64),
     Uniform { min: f64, max: f64 },
     Reciprocal { min: f64, max: f64 },
+    Hadronic { collider: Collider, min: f64 },
 }
 
-impl From<&PyScale> for Scale<f64> {
-    fn from(value: &PyScale) -> Self {
-        match value {
-            PyScale::Fixed(s) => Scale::Fixed(*s),
-            PyScale::Uniform { min, max } => Scale::Uniform {
-                min: *min,
-                max: *max,
-            },
-            PyScale::Reciprocal { min, max } => Scale::Reciprocal {
-                min: *min,
-                max: *max,
-            },
-        }
+impl PyScale {
+    /// Number of random numbers consumed by `generate` for `n_out` outgoing particles
+    fn dimension(&self, n_out: usize) -> usize {
+        return match self {
+            PyScale::Fixed(s) => dimension(&Scale::Fixed(*s), n_out),
+            PyScale::Uniform { min, max } | PyScale::Reciprocal { min, max } => dimension(
+                &Scale::Uniform {
+                    min: *min,
+                    max: *max,
+                },
+                n_out,
+            ),
+            PyScale::Hadronic { collider, .. } => {
+                collider.dimension() + dimension(&Scale::Fixed(0.), n_out)
+            }
+        };
+    }
+
+    /// Generate a RAMBO point for the incoming partons `pdg_in` and the masses `masses` in the arithmetic
+    /// `precision`. For a hadronic scale, the partonic point is boosted to the lab frame and its weight includes the
+    /// parton luminosity.
+    fn generate(
+        &self,
+        pdg_in: &[i64],
+        masses: &[f64],
+        precision: Precision,
+        rng: &mut impl FloatRandom<f64>,
+    ) -> GeneratedPoint {
+        return match precision {
+            Precision::Double => self.generate_in::<f64>(pdg_in, masses, rng),
+            Precision::DoubleDouble => {
+                self.generate_in::<DoubleDouble>(pdg_in, masses, &mut DoubleDoubleRandom(rng))
+            }
+        };
+    }
+
+    /// Generate a RAMBO point in the floating point type `F` and round it to `f64`, together with its violations
+    /// computed in `F`
+    fn generate_in<F: Float + FloatConst>(
+        &self,
+        pdg_in: &[i64],
+        masses: &[f64],
+        rng: &mut impl FloatRandom<F>,
+    ) -> GeneratedPoint {
+        let n_in = pdg_in.len();
+        let cast = |x: f64| F::from(x).unwrap();
+        let masses = masses.iter().map(|m| cast(*m)).collect::<Vec<_>>();
+        let (s, vecs, weight) = match self {
+            PyScale::Hadronic { collider, min } => {
+                let lumi = collider.sample(pdg_in, *min, rng);
+                let (s, mut vecs, weight) = rambo(Scale::Fixed(cast(lumi.s)), &masses, n_in, rng);
+                boost_z(&mut vecs, cast(lumi.rapidity));
+                (s, vecs, weight * cast(lumi.weight))
+            }
+            PyScale::Fixed(s) => rambo(Scale::Fixed(cast(*s)), &masses, n_in, rng),
+            PyScale::Uniform { min, max } => rambo(
+                Scale::Uniform {
+                    min: cast(*min),
+                    max: cast(*max),
+                },
+                &masses,
+                n_in,
+                rng,
+            ),
+            PyScale::Reciprocal { min, max } => rambo(
+                Scale::Reciprocal {
+                    min: cast(*min),
+                    max: cast(*max),
+                },
+                &masses,
+                n_in,
+                rng,
+            ),
+        };
+        let (conservation, on_shell) = momentum_violation(&vecs, &masses, n_in);
+        let round = |x: F| x.to_f64().unwrap();
+        return (
+            round(s),
+            vecs.iter().map(|p| p.map(round)).collect(),
+            round(weight),
+            [round(conservation), round(on_shell)],
+        );
     }
 }
 
+/// Arithmetic precision of the phase-space generation. Points generated in double-double precision conserve momentum
+/// and are on-shell to about 30 digits before they are rounded to double precision for the OLP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[pyclass]
+pub(crate) enum Precision {
+    /// Generation in `f64` arithmetic
+    Double,
+    /// Generation in double-double arithmetic with about 32 significant digits
+    DoubleDouble,
+}
+
+/// Process evaluated by a one-loop provider, the common base of `GoSamProcess` and `BLHAProcess` providing the
+/// evaluation and sampling methods
+#[pyclass(subclass)]
+pub(crate) struct Process {
+    nlo_coupling: Option<String>,
+    subprocesses: Vec<Subprocess>,
+    model: Model,
+    accuracy_threshold: Option<f64>,
+    parameters: IndexMap<String, (f64, f64)>,
+    vegas_grids: IndexMap<usize, VegasGrid>,
+    seed: u64,
+    n_calls: AtomicU64,
+    last_seed: AtomicU64,
+    cuts: Vec<Cut>,
+    cut_efficiency: AtomicU64,
+    rescue: Vec<Rescue>,
+    discard_unstable: bool,
+    /// Number of stable, rescued and unstable points of the last sampling call
+    point_statistics: [AtomicU64; 3],
+    /// Number of unstable points discarded by the last sampling call
+    n_discarded: AtomicU64,
+    precision: Precision,
+    /// Tolerance of the relative violations of momentum conservation and of the mass shells of user-supplied points
+    point_tolerance: Option<f64>,
+    project_points: bool,
+    /// OLP ids of the channels assigned in the contract to the subprocesses, indexed by the id of `add_subprocess`
+    olp_ids: Vec<Vec<i64>>,
+    olp: Option<Box<dyn OLP>>,
+}
+
+/// Process whose library is generated by GoSam for the subprocesses added with `add_subprocess`
+#[pyclass(extends = Process)]
+#[derive(Clone)]
 pub(crate) struct GoSamProcess {
     coupling_orders: IndexMap<String, usize>,
-    nlo_coupling: Option<String>,
     contract_options: Option<IndexMap<String, String>>,
     gosam_options: Option<IndexMap<String, String>>,
-    subprocesses: Vec<Subprocess>,
-    model: Model,
-    olp: Option<OneLoopProvider>,
 }
 
-impl Hash for GoSamProcess {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.coupling_orders.iter().collect::<Vec<_>>().hash(state);
-        if let Some(ref opts) = self.contract_options {
-            opts.iter().collect::<Vec<_>>().hash(state);
+impl Process {
+    fn new(
+        model: Model,
+        nlo_coupling: Option<String>,
+        accuracy_threshold: Option<f64>,
+        seed: Option<u64>,
+    ) -> Self {
+        let seed = seed.unwrap_or_else(|| fastrand::u64(..));
+        return Process {
+            nlo_coupling,
+            subprocesses: vec![],
+            model,
+            accuracy_threshold,
+            parameters: IndexMap::new(),
+            vegas_grids: IndexMap::new(),
+            seed,
+            n_calls: AtomicU64::new(0),
+            last_seed: AtomicU64::new(seed),
+            cuts: Vec::new(),
+            cut_efficiency: AtomicU64::new(1_f64.to_bits()),
+            rescue: Vec::new(),
+            discard_unstable: false,
+            point_statistics: Default::default(),
+            n_discarded: AtomicU64::new(0),
+            precision: Precision::Double,
+            point_tolerance: Some(1E-6),
+            project_points: false,
+            olp_ids: Vec::new(),
+            olp: None,
+        };
+    }
+
+    /// Evaluate a single point and flag it as unstable if the accuracy reported by the OLP is worse than the
+    /// accuracy threshold of the process, falling back to the `AccuracyTarget` of the contract. Unstable points are
+    /// passed through the rescue steps of the process. The channels of the subprocess are summed.
+    fn eval_point(
+        &self,
+        olp: &dyn OLP,
+        id: usize,
+        momenta: &[[f64; 4]],
+        scale: f64,
+    ) -> Result<EvalResult, BLHAError> {
+        return Ok(EvalResult::sum(
+            self.eval_point_channels(olp, id, momenta, scale)?,
+        ));
+    }
+
+    /// Evaluate a single point in each channel of subprocess `id` separately, see `eval_point`
+    fn eval_point_channels(
+        &self,
+        olp: &dyn OLP,
+        id: usize,
+        momenta: &[[f64; 4]],
+        scale: f64,
+    ) -> Result<Vec<EvalResult>, BLHAError> {
+        let threshold = self.accuracy_threshold.or_else(|| olp.accuracy_target());
+        let amplitude_type = self.subprocesses[id].amplitude_type;
+        // A panic while holding the lock leaves no partial state behind that would need to be discarded
+        let _guard = olp.eval_lock().lock().unwrap_or_else(|e| e.into_inner());
+        let mut channels = Vec::with_capacity(self.olp_ids[id].len());
+        for &olp_id in self.olp_ids[id].iter() {
+            let mut res = olp.eval(olp_id, momenta, scale)?;
+            res.check_accuracy(threshold);
+            channels.push(rescue(&self.rescue, threshold, res, |step| {
+                step.eval(olp, olp_id, amplitude_type, momenta, scale)
+            })?);
         }
-        if let Some(ref opts) = self.gosam_options {
-            opts.iter().collect::<Vec<_>>().hash(state);
+        return Ok(channels);
+    }
+
+    fn get_olp(&self, method: &str) -> Result<&dyn OLP, GoSamError> {
+        if let Some(ref olp) = self.olp {
+            return Ok(olp.as_ref());
+        } else {
+            return Err(GoSamError::UnintializedError(method.to_owned()));
+        }
+    }
+
+    fn masses(&self, id: usize) -> Vec<f64> {
+        return self.subprocesses[id]
+            .incoming_pdg
+            .iter()
+            .chain(self.subprocesses[id].outgoing_pdg.iter())
+            .map(|i| self.model.get_mass(*i))
+            .collect();
+    }
+
+    /// Check the user-supplied point `vecs` of subprocess `id` against the masses of the model and momentum
+    /// conservation within the point tolerance. If requested, the point is projected onto the mass shells and exact
+    /// momentum conservation first, such that only points that cannot be projected are rejected.
+    fn prepare_point(&self, id: usize, vecs: &[[f64; 4]]) -> Result<Vec<[f64; 4]>, GoSamError> {
+        let n_legs = self.subprocesses[id].n_legs();
+        if vecs.len() != n_legs {
+            return Err(GoSamError::ShapeError(format!(
+                "Subprocess {id} has {n_legs} legs, got {} momenta",
+                vecs.len()
+            )));
+        }
+        let masses = self.masses(id);
+        let n_in = self.subprocesses[id].incoming_pdg.len();
+        let vecs = if self.project_points {
+            project(vecs, &masses, n_in).ok_or_else(|| {
+                GoSamError::PointError(format!(
+                    "Incoming momenta are below the threshold of the outgoing masses {:?}",
+                    &masses[n_in..]
+                ))
+            })?
+        } else {
+            vecs.to_vec()
+        };
+        if let Some(tolerance) = self.point_tolerance {
+            let (conservation, on_shell) = momentum_violation(&vecs, &masses, n_in);
+            if conservation > tolerance || conservation.is_nan() {
+                return Err(GoSamError::PointError(format!(
+                    "Momentum conservation is violated by {conservation:e} relative to the incoming energy, \
+                     tolerance {tolerance:e}"
+                )));
+            }
+            if on_shell > tolerance || on_shell.is_nan() {
+                return Err(GoSamError::PointError(format!(
+                    "Momenta are off-shell by {on_shell:e} relative to their energies, masses {masses:?}, \
+                     tolerance {tolerance:e}"
+                )));
+            }
+        }
+        return Ok(vecs);
+    }
+
+    /// Load an independent copy of the process library into `dir` and apply all parameters set so far
+    fn load_worker(
+        &self,
+        olp: &dyn OLP,
+        dir: &Path,
+        index: usize,
+    ) -> Result<Box<dyn OLP>, BLHAError> {
+        let worker = olp.load_copy(dir, index)?;
+        for (parameter, (real, imag)) in self.parameters.iter() {
+            worker.set_parameter(parameter, *real, *imag)?;
+        }
+        return Ok(worker);
+    }
+
+    /// Determine the seed of a sampling call. Calls without an explicit seed draw consecutive substreams of the
+    /// process seed, such that a sequence of calls is reproducible from the process seed alone.
+    fn call_seed(&self, seed: Option<u64>) -> u64 {
+        let seed = seed.unwrap_or_else(|| {
+            substream(self.seed, self.n_calls.fetch_add(1, Ordering::Relaxed)).u64(..)
+        });
+        self.last_seed.store(seed, Ordering::Relaxed);
+        return seed;
+    }
+
+    /// Fraction of the points of the last call to `sample_with` that passed the cuts
+    fn cut_efficiency(&self) -> f64 {
+        return f64::from_bits(self.cut_efficiency.load(Ordering::Relaxed));
+    }
+
+    /// Check whether the point `vecs` of subprocess `id` passes all cuts
+    fn passes_cuts(&self, id: usize, vecs: &[[f64; 4]]) -> PyResult<bool> {
+        if self.cuts.is_empty() {
+            return Ok(true);
+        }
+        let subprocess = &self.subprocesses[id];
+        let pdg = subprocess
+            .incoming_pdg
+            .iter()
+            .chain(subprocess.outgoing_pdg.iter())
+            .copied()
+            .collect::<Vec<_>>();
+        for cut in self.cuts.iter() {
+            if !cut.passes(vecs, &pdg, subprocess.incoming_pdg.len())? {
+                return Ok(false);
+            }
+        }
+        return Ok(true);
+    }
+
+    /// Evaluate subprocess `id` at `n_points` RAMBO points and pass each point to `f`, displaying a progress bar
+    /// if `tqdm` is available. Points failing the cuts are passed as `Sampled::Cut`, and unstable points as
+    /// `Sampled::Discarded` if they are discarded. If `n_threads` is larger than one, the points are distributed over
+    /// `n_threads` independent instances of the process library. Returns the fraction of the points that passed the
+    /// cuts, including discarded points.
+    ///
+    /// The points are generated in chunks of `CHUNK_SIZE` points, where chunk `k` is drawn from substream `k` of
+    /// `seed`. The sampled points are therefore independent of the number of threads.
+    fn sample_with(
+        &self,
+        py: Python<'_>,
+        id: usize,
+        n_points: usize,
+        settings: SampleSettings<'_>,
+        mut f: impl FnMut(&[[f64; 4]], f64, Sampled) + Send,
+    ) -> PyResult<f64> {
+        let SampleSettings {
+            s,
+            frame,
+            scale,
+            seed,
+            n_threads,
+            progress,
+        } = settings;
+        let seed = self.call_seed(seed);
+        let lib = self.get_olp("sample")?;
+        self.check_scale(id, s)?;
+        self.check_grid(id, s)?;
+        self.check_frame(id, s, frame)?;
+        let masses = self.masses(id);
+        let n_in = self.subprocesses[id].incoming_pdg.len();
+        let n_threads = n_threads.unwrap_or(1).clamp(1, n_points.max(1));
+        if n_threads > 1 && !lib.isolated_copies() {
+            return Err(GoSamError::ProcessError(
+                "Copies of the OLP library are not isolated from each other, use a single thread"
+                    .to_owned(),
+            ))?;
+        }
+
+        let tqdm = match py.import("tqdm.auto") {
+            Ok(m) if progress => Some(
+                m.getattr("tqdm")?
+                    .call((), Some(&[("total", n_points)].into_py_dict(py)?))?
+                    .unbind(),
+            ),
+            _ => None,
+        };
+        let mut update = None;
+        if let Some(ref tqdm) = tqdm {
+            update =
+                Some(|inc: usize| Python::with_gil(|py| tqdm.call_method1(py, "update", (inc,))))
+        }
+        let n_update = if n_points >= 1000 { n_points / 1000 } else { 1 };
+        let n_chunks = n_points.div_ceil(CHUNK_SIZE);
+        let worker = |olp: &dyn OLP,
+                      chunks: std::ops::Range<usize>,
+                      f: &mut PointCallback<'_>|
+         -> PyResult<()> {
+            let mut i = 0;
+            for chunk in chunks {
+                let mut rng = substream(seed, chunk as u64);
+                for _ in (chunk * CHUNK_SIZE)..((chunk + 1) * CHUNK_SIZE).min(n_points) {
+                    let (mut renorm_scale, mut vecs, weight, _) =
+                        self.generate(id, s, &masses, &mut rng);
+                    if let Some(frame) = frame {
+                        frame.apply(&mut vecs, n_in, &mut rng);
+                    }
+                    if let Some(scale) = scale {
+                        renorm_scale = scale;
+                    }
+                    if self.passes_cuts(id, &vecs)? {
+                        let vals = self.eval_point(olp, id, &vecs, renorm_scale)?;
+                        if self.discard_unstable && vals.unstable() {
+                            f(&vecs, weight, Sampled::Discarded(vals));
+                        } else {
+                            f(&vecs, weight, Sampled::Evaluated(vals));
+                        }
+                    } else {
+                        f(&vecs, weight, Sampled::Cut);
+                    }
+                    if i % n_update == 0
+                        && let Some(f) = update
+                    {
+                        f(n_update)?;
+                    }
+                    i += 1;
+                }
+            }
+            return Ok(());
+        };
+        let mut n_passed = 0;
+        let mut n_discarded = 0;
+        let mut statistics = [0; 3];
+        let mut f = |vecs: &[[f64; 4]], weight: f64, point: Sampled| {
+            match point {
+                Sampled::Evaluated(ref vals) => {
+                    statistics[vals.status as usize] += 1;
+                    n_passed += 1;
+                }
+                Sampled::Discarded(ref vals) => {
+                    statistics[vals.status as usize] += 1;
+                    n_passed += 1;
+                    n_discarded += 1;
+                }
+                Sampled::Cut => {}
+            }
+            f(vecs, weight, point)
+        };
+        let result = py.allow_threads(|| -> PyResult<_> {
+            if n_threads == 1 {
+                return worker(lib, 0..n_chunks, &mut f);
+            }
+            let dir = tempfile::tempdir()?;
+            let copies = (1..n_threads)
+                .map(|i| self.load_worker(lib, dir.path(), i))
+                .collect::<Result<Vec<_>, _>>()?;
+            let points = std::thread::scope(|scope| {
+                let handles = std::iter::once(lib)
+                    .chain(copies.iter().map(|copy| copy.as_ref()))
+                    .enumerate()
+                    .map(|(i, olp)| {
+                        let chunks = (i * n_chunks / n_threads)..((i + 1) * n_chunks / n_threads);
+                        scope.spawn(move || -> PyResult<_> {
+                            let mut points = Vec::with_capacity(chunks.len() * CHUNK_SIZE);
+                            worker(olp, chunks, &mut |vecs, weight, point| {
+                                points.push((vecs.to_vec(), weight, point))
+                            })?;
+                            return Ok(points);
+                        })
+                    })
+                    .collect::<Vec<_>>();
+                return handles
+                    .into_iter()
+                    .map(|handle| handle.join().unwrap())
+                    .collect::<PyResult<Vec<_>>>();
+            })?;
+            for (vecs, weight, point) in points.into_iter().flatten() {
+                f(&vecs, weight, point);
+            }
+            return Ok(());
+        });
+        if let Some(ref tqdm) = tqdm {
+            tqdm.call_method0(py, "close")?;
+        }
+        result?;
+        let efficiency = if n_points > 0 {
+            n_passed as f64 / n_points as f64
+        } else {
+            1.
+        };
+        self.cut_efficiency
+            .store(efficiency.to_bits(), Ordering::Relaxed);
+        self.n_discarded.store(n_discarded, Ordering::Relaxed);
+        for (stored, n) in self.point_statistics.iter().zip(statistics) {
+            stored.store(n, Ordering::Relaxed);
+        }
+        return Ok(efficiency);
+    }
+
+    /// Flux factor of subprocess `id` at center of mass energy squared `s`, optionally including the symmetry factor
+    /// of identical final-state particles and the average over initial-state spins and colours
+    fn normalization(&self, id: usize, s: f64, symmetry_factor: bool, average: bool) -> f64 {
+        let subprocess = &self.subprocesses[id];
+        let masses = self.masses(id);
+        let mut factor = if subprocess.incoming_pdg.len() == 1 {
+            1. / (2. * masses[0])
+        } else {
+            let (m1_sq, m2_sq) = (masses[0] * masses[0], masses[1] * masses[1]);
+            let lambda = (s - m1_sq - m2_sq).powi(2) - 4. * m1_sq * m2_sq;
+            1. / (2. * lambda.sqrt())
+        };
+        if symmetry_factor {
+            let mut counts = IndexMap::<i64, usize>::new();
+            for pdg in subprocess.outgoing_pdg.iter() {
+                *counts.entry(*pdg).or_default() += 1;
+            }
+            for n in counts.values() {
+                factor /= (1..=*n).product::<usize>() as f64;
+            }
+        }
+        if average {
+            for pdg in subprocess.incoming_pdg.iter() {
+                factor /= self.model.degrees_of_freedom(*pdg) as f64;
+            }
+        }
+        return factor;
+    }
+
+    /// Component of subprocess `id` used as integrand, defaulting to the Born for `Tree` and the finite part otherwise
+    fn component(&self, id: usize, component: Option<Component>) -> Result<Component, GoSamError> {
+        return match (self.subprocesses[id].amplitude_type, component) {
+            (AmplitudeType::Tree, None) => Ok(Component::Born),
+            (AmplitudeType::Loop | AmplitudeType::LoopInduced, None) => Ok(Component::Finite),
+            (AmplitudeType::Tree | AmplitudeType::Loop | AmplitudeType::LoopInduced, Some(c)) => {
+                Ok(c)
+            }
+            (t, _) => Err(GoSamError::IntegrandError(format!(
+                "Amplitude type {t} can not be integrated"
+            ))),
+        };
+    }
+
+    /// Check that `s` is fixed to the squared mass of the decaying particle for `1 -> n` subprocesses and of the
+    /// produced particle for `n -> 1` subprocesses, and that hadronic scales have a positive partonic threshold
+    fn check_scale(&self, id: usize, s: &PyScale) -> Result<(), GoSamError> {
+        if let PyScale::Hadronic { min, .. } = s
+            && (*min <= 0. || min.is_nan())
+        {
+            return Err(GoSamError::ScaleError(format!(
+                "Minimal partonic center of mass energy squared must be positive, got {min:e}"
+            )));
+        }
+        let subprocess = &self.subprocesses[id];
+        let masses = self.masses(id);
+        let m = if subprocess.outgoing_pdg.len() == 1 {
+            masses[masses.len() - 1]
+        } else if subprocess.incoming_pdg.len() == 1 {
+            masses[0]
+        } else {
+            return Ok(());
+        };
+        if m == 0. {
+            return Err(GoSamError::ScaleError(format!(
+                "Subprocess {id} requires a massive {} particle",
+                if subprocess.outgoing_pdg.len() == 1 {
+                    "outgoing"
+                } else {
+                    "decaying"
+                }
+            )));
+        }
+        match s {
+            PyScale::Fixed(s) if (s / (m * m) - 1.).abs() < 1E-10 => return Ok(()),
+            _ => {
+                return Err(GoSamError::ScaleError(format!(
+                    "Subprocess {id} requires the fixed scale s = {:e}",
+                    m * m
+                )));
+            }
+        }
+    }
+
+    /// Check that the VEGAS grid of subprocess `id`, if any, matches the dimension of the phase space
+    fn check_grid(&self, id: usize, s: &PyScale) -> Result<(), GoSamError> {
+        if let Some(grid) = self.vegas_grids.get(&id) {
+            let dim = s.dimension(self.subprocesses[id].outgoing_pdg.len());
+            if grid.dim() != dim {
+                return Err(GoSamError::GridError(format!(
+                    "VEGAS grid of subprocess {id} has dimension {}, but the phase space has dimension {dim}",
+                    grid.dim()
+                )));
+            }
+        }
+        return Ok(());
+    }
+
+    /// Check that the leg of a `Rest` of subprocess `id` exists and is massive
+    /// Check that subprocess `id` exists and, once the OLP is loaded, is part of its contract
+    fn check_id(&self, id: usize) -> Result<(), GoSamError> {
+        if self.subprocesses.get(id).is_none() {
+            return Err(GoSamError::IndexError(format!(
+                "Subprocess {id} out of range for process with {} subprocesses",
+                self.subprocesses.len()
+            )));
+        }
+        if self.olp.is_some() && self.olp_ids.get(id).is_none() {
+            return Err(GoSamError::ProcessError(format!(
+                "Subprocess {id} was added after the setup of the process"
+            )));
+        }
+        return Ok(());
+    }
+
+    fn check_frame(&self, id: usize, s: &PyScale, frame: Option<&Frame>) -> Result<(), GoSamError> {
+        if let Some(Frame::Lab { energy1, energy2 }) = frame {
+            if self.subprocesses[id].incoming_pdg.len() != 2 {
+                return Err(GoSamError::FrameError(format!(
+                    "Subprocess {id} has no two incoming particles to collide"
+                )));
+            }
+            if !(energy1.is_finite() && energy2.is_finite() && *energy1 > 0. && *energy2 > 0.) {
+                return Err(GoSamError::FrameError(format!(
+                    "Beam energies {energy1:e} and {energy2:e} are not positive"
+                )));
+            }
+            // The beams determine the partonic center of mass energy
+            let beams = 4. * energy1 * energy2;
+            match s {
+                PyScale::Fixed(s) if (beams - s).abs() <= 1E-12 * s => {}
+                PyScale::Fixed(s) => {
+                    return Err(GoSamError::FrameError(format!(
+                        "Beam energies {energy1:e} and {energy2:e} do not match s = {s:e}"
+                    )));
+                }
+                _ => {
+                    return Err(GoSamError::FrameError(
+                        "The lab frame requires a fixed scale".to_owned(),
+                    ));
+                }
+            }
+        }
+        if let Some(Frame::Rest { index }) = frame {
+            let masses = self.masses(id);
+            if *index >= masses.len() {
+                return Err(GoSamError::FrameError(format!(
+                    "Subprocess {id} has no leg {index}"
+                )));
+            }
+            if masses[*index] == 0. {
+                return Err(GoSamError::FrameError(format!(
+                    "Leg {index} of subprocess {id} is massless and has no rest frame"
+                )));
+            }
+        }
+        return Ok(());
+    }
+
+    /// Generate a RAMBO point for subprocess `id`, drawn from the adapted density if a VEGAS grid is present
+    fn generate(
+        &self,
+        id: usize,
+        s: &PyScale,
+        masses: &[f64],
+        rng: &mut fastrand::Rng,
+    ) -> GeneratedPoint {
+        let pdg_in = &self.subprocesses[id].incoming_pdg;
+        if let Some(grid) = self.vegas_grids.get(&id) {
+            let u = (0..grid.dim()).map(|_| rng.f64()).collect::<Vec<_>>();
+            let mut x = vec![0.; grid.dim()];
+            let jacobian = grid.map(&u, &mut x);
+            let (s, vecs, weight, violation) =
+                s.generate(pdg_in, masses, self.precision, &mut Hypercube::new(&x));
+            return (s, vecs, weight * jacobian, violation);
+        }
+        return s.generate(pdg_in, masses, self.precision, rng);
+    }
+
+    /// Generate points of subprocess `id` until one passes the cuts, giving up after `MAX_CUT_TRIES` points
+    fn generate_accepted(
+        &self,
+        id: usize,
+        s: &PyScale,
+        frame: Option<&Frame>,
+        masses: &[f64],
+        rng: &mut fastrand::Rng,
+    ) -> PyResult<GeneratedPoint> {
+        let n_in = self.subprocesses[id].incoming_pdg.len();
+        for _ in 0..MAX_CUT_TRIES {
+            let mut point = self.generate(id, s, masses, rng);
+            if let Some(frame) = frame {
+                frame.apply(&mut point.1, n_in, rng);
+            }
+            if self.passes_cuts(id, &point.1)? {
+                return Ok(point);
+            }
+        }
+        return Err(GoSamError::IntegrandError(format!(
+            "No point of subprocess {id} passed the cuts in {MAX_CUT_TRIES} tries"
+        )))?;
+    }
+
+    /// Strong coupling set with `set_parameter`, if any
+    fn alpha_s(&self) -> Option<f64> {
+        return self
+            .parameters
+            .iter()
+            .find(|(name, _)| matches!(name.to_lowercase().as_str(), "alphas" | "alpha_s"))
+            .map(|(_, (real, _))| *real);
+    }
+
+    /// Coloured legs of subprocess `id` if its infrared poles are known, i.e. for QCD corrections with only massless
+    /// coloured particles
+    fn coloured_legs(&self, id: usize) -> Option<Vec<ColouredLeg>> {
+        if !self
+            .nlo_coupling
+            .as_ref()
+            .is_some_and(|c| c.eq_ignore_ascii_case("QCD"))
+        {
+            return None;
+        }
+        let subprocess = &self.subprocesses[id];
+        let n_f = self.model.n_light_flavours();
+        let mut legs = Vec::new();
+        for (index, pdg) in subprocess
+            .incoming_pdg
+            .iter()
+            .chain(subprocess.outgoing_pdg.iter())
+            .enumerate()
+        {
+            let color = self.model.color(*pdg);
+            if color.abs() == 1 {
+                continue;
+            }
+            if self.model.get_mass(*pdg) != 0. {
+                return None;
+            }
+            legs.push(ColouredLeg::new(index, color, n_f)?);
+        }
+        return Some(legs);
+    }
+
+    /// `ccTree` subprocess with the same particles as subprocess `id`, if any
+    fn correlated_subprocess(&self, id: usize) -> Option<usize> {
+        let subprocess = &self.subprocesses[id];
+        return self.subprocesses.iter().position(|other| {
+            other.amplitude_type == AmplitudeType::ccTree
+                && other.incoming_pdg == subprocess.incoming_pdg
+                && other.outgoing_pdg == subprocess.outgoing_pdg
+        });
+    }
+
+    /// Subprocess with the same amplitude type and outgoing particles as subprocess `id` and exchanged incoming
+    /// particles, if any. This is subprocess `id` itself for identical incoming particles. Only unpolarized amplitudes
+    /// without colour correlations are invariant under the exchange.
+    fn crossed_subprocess(&self, id: usize) -> Option<usize> {
+        let subprocess = &self.subprocesses[id];
+        if subprocess.incoming_pdg.len() != 2
+            || !matches!(
+                subprocess.amplitude_type,
+                AmplitudeType::Tree | AmplitudeType::Loop | AmplitudeType::LoopInduced
+            )
+        {
+            return None;
         }
-        self.nlo_coupling.hash(state);
-        self.subprocesses.hash(state);
+        let incoming = [subprocess.incoming_pdg[1], subprocess.incoming_pdg[0]];
+        return self.subprocesses.iter().position(|other| {
+            other.amplitude_type == subprocess.amplitude_type
+                && other.incoming_pdg == incoming
+                && other.outgoing_pdg == subprocess.outgoing_pdg
+                && other.coupling_powers == subprocess.coupling_powers
+        });
+    }
+
+    /// Whether the colour correlators of the coloured legs `legs` of subprocess `id` are available
+    fn correlators_known(&self, id: usize, legs: &[ColouredLeg]) -> bool {
+        return legs.len() < 2
+            || self.correlated_subprocess(id).is_some()
+            || casimir_correlator(legs, 0, 1).is_some();
+    }
+
+    /// Double and single pole of the `Loop` subprocess `id` at the point `vecs` predicted by the I-operator from the
+    /// Born `born`. The colour correlators are taken from the matching `ccTree` subprocess if available and are
+    /// otherwise fixed by colour conservation. The single pole is `None` if the colour correlators are unknown.
+    #[allow(clippy::too_many_arguments)]
+    fn predicted_poles(
+        &self,
+        olp: &dyn OLP,
+        id: usize,
+        vecs: &[[f64; 4]],
+        mu: f64,
+        born: f64,
+        legs: &[ColouredLeg],
+        alpha_s: f64,
+    ) -> Result<(f64, Option<f64>), BLHAError> {
+        let cc = match self.correlated_subprocess(id) {
+            Some(cc_id) => match self.typed(cc_id, self.eval_point(olp, cc_id, vecs, mu)?) {
+                TypedResult::ColorCorrelated(cc) => Some(cc),
+                _ => unreachable!(),
+            },
+            None => None,
+        };
+        let correlator = |i: usize, j: usize| match cc {
+            Some(ref cc) => Some(cc.get(legs[i].index, legs[j].index)),
+            None => casimir_correlator(legs, i, j).map(|c| c * born),
+        };
+        let n_in = self.subprocesses[id].incoming_pdg.len();
+        let (double_pole, single_pole) = ir_poles(
+            vecs,
+            n_in,
+            legs,
+            born,
+            |i, j| correlator(i, j).unwrap_or(0.),
+            mu,
+        );
+        let a_s = alpha_s / (2. * std::f64::consts::PI);
+        return Ok((
+            a_s * double_pole,
+            self.correlators_known(id, legs)
+                .then_some(a_s * single_pole),
+        ));
+    }
+
+    fn typed(&self, id: usize, res: EvalResult) -> TypedResult {
+        let subprocess = &self.subprocesses[id];
+        return res.typed(subprocess.amplitude_type, subprocess.n_legs());
     }
 }
 
 impl GoSamProcess {
-    fn write_order(&self) -> Result<(), GoSamError> {
-        let options = if let Some(ref contract_options) = self.contract_options {
+    /// Hash of the configuration of the process library of `process`, which names its build directory
+    fn library_hash(&self, process: &Process) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.coupling_orders
+            .iter()
+            .collect::<Vec<_>>()
+            .hash(&mut hasher);
+        if let Some(ref opts) = self.contract_options {
+            opts.iter().collect::<Vec<_>>().hash(&mut hasher);
+        }
+        if let Some(ref opts) = self.gosam_options {
+            opts.iter().collect::<Vec<_>>().hash(&mut hasher);
+        }
+        process.nlo_coupling.hash(&mut hasher);
+        process.subprocesses.hash(&mut hasher);
+        return hasher.finish();
+    }
+
+    fn write_order(&self, process: &Process) -> Result<(), GoSamError> {
+        let mut options = if let Some(ref contract_options) = self.contract_options {
             let mut tmp = contract_options.clone();
             tmp.insert(
                 "CorrectionType".to_owned(),
-                if let Some(ref nlo) = self.nlo_coupling {
+                if let Some(ref nlo) = process.nlo_coupling {
                     nlo.clone()
                 } else {
                     "QCD".to_owned()
@@ -116,18 +1020,23 @@ impl GoSamProcess {
         } else {
             IndexMap::from([(
                 "CorrectionType".to_owned(),
-                if let Some(ref nlo) = self.nlo_coupling {
+                if let Some(ref nlo) = process.nlo_coupling {
                     nlo.clone()
                 } else {
                     "QCD".to_owned()
                 },
             )])
         };
+        let interface_version = match options.shift_remove("InterfaceVersion") {
+            Some(version) => version.parse()?,
+            None => InterfaceVersion::BLHA2,
+        };
         let order = Order {
+            interface_version,
             coupling_orders: self.coupling_orders.clone(),
-            model: &self.model,
-            nlo_coupling: self.nlo_coupling.clone(),
-            subprocesses: &self.subprocesses,
+            model: &process.model,
+            nlo_coupling: process.nlo_coupling.clone(),
+            subprocesses: &process.subprocesses,
             options,
         };
         crate::blha::order_writer::write_order_file(
@@ -168,10 +1077,8 @@ impl GoSamProcess {
         Ok(())
     }
 
-    fn setup_process(&mut self) -> Result<(), GoSamError> {
-        let mut hasher = DefaultHasher::new();
-        self.hash(&mut hasher);
-        let hash = hasher.finish();
+    fn setup_process(&self, process: &mut Process) -> Result<(), GoSamError> {
+        let hash = self.library_hash(process);
         if !std::fs::exists("gosam_process")? {
             std::fs::create_dir("gosam_process")?;
         }
@@ -181,36 +1088,83 @@ impl GoSamProcess {
             std::fs::create_dir(&process_path)?;
         }
         if std::fs::exists(&process_path.join("build/libgolem_olp.so"))? {
-            self.olp = Some(OneLoopProvider::new(
+            return Self::load_olp(
+                process,
                 &process_path.join("gosam.olc"),
                 &process_path.join("build/libgolem_olp.so"),
-            )?);
-            return Ok(());
+            );
         }
         std::env::set_current_dir(&process_path)?;
-        self.write_order()?;
+        self.write_order(process)?;
         self.run_gosam()?;
         self.compile_process_libaray()?;
-        self.olp = Some(OneLoopProvider::new(
+        let loaded = Self::load_olp(
+            process,
             &std::env::current_dir()?.join("gosam.olc"),
             &std::env::current_dir()?.join("build/libgolem_olp.so"),
-        )?);
+        );
         std::env::set_current_dir(working_path)?;
-        Ok(())
+        return loaded;
     }
-}
 
-#[pymethods]
-impl GoSamProcess {
-    #[new]
-    #[pyo3(signature = (coupling_orders, model, nlo_coupling = None, contract_options = None, gosam_options = None))]
-    fn new(
-        coupling_orders: IndexMap<String, usize>,
+    /// Load the process library and match the subprocesses to the ids assigned in the contract
+    fn load_olp(
+        process: &mut Process,
+        contract_path: &Path,
+        library_path: &Path,
+    ) -> Result<(), GoSamError> {
+        // GoSam libraries are self-contained and return tree amplitudes in the layout of loop amplitudes
+        let olp = OneLoopProvider::new(contract_path, library_path, TreeLayout::Loop, true)?;
+        process.olp_ids = olp.olp_ids(&process.subprocesses)?;
+        process.olp = Some(Box::new(olp));
+        return Ok(());
+    }
+
+    /// Subprocess `incoming -> outgoing` of the amplitude type `amplitude_type`, whose coupling powers override the
+    /// coupling orders of the process
+    fn subprocess(
+        &self,
+        incoming: Vec<i64>,
+        outgoing: Vec<i64>,
+        amplitude_type: AmplitudeType,
+        coupling_powers: Option<IndexMap<String, usize>>,
+    ) -> Result<Subprocess, GoSamError> {
+        let coupling_powers = coupling_powers.unwrap_or_default();
+        if let Some(coupling) = coupling_powers
+            .keys()
+            .find(|c| !self.coupling_orders.contains_key(*c))
+        {
+            return Err(GoSamError::ProcessError(format!(
+                "Coupling {coupling} has no order in the process"
+            )));
+        }
+        return Ok(Subprocess {
+            ids: Vec::new(),
+            coupling_powers: coupling_powers
+                .into_iter()
+                .map(|(coupling, power)| (coupling, power as i64))
+                .collect(),
+            options: IndexMap::new(),
+            incoming_pdg: incoming,
+            outgoing_pdg: outgoing,
+            amplitude_type,
+        });
+    }
+}
+
+#[pymethods]
+impl GoSamProcess {
+    #[new]
+    #[pyo3(signature = (coupling_orders, model, nlo_coupling = None, contract_options = None, gosam_options = None, accuracy_threshold = None, seed = None))]
+    fn new(
+        coupling_orders: IndexMap<String, usize>,
         model: Model,
         nlo_coupling: Option<String>,
         contract_options: Option<IndexMap<String, Bound<'_, PyAny>>>,
         gosam_options: Option<IndexMap<String, Bound<'_, PyAny>>>,
-    ) -> PyResult<Self> {
+        accuracy_threshold: Option<f64>,
+        seed: Option<u64>,
+    ) -> PyResult<(Self, Process)> {
         let contract_opts;
         if let Some(options) = contract_options {
             let mut map = IndexMap::with_capacity(options.len());
@@ -221,6 +1175,14 @@ impl GoSamProcess {
         } else {
             contract_opts = None;
         }
+        let version = contract_opts
+            .as_ref()
+            .and_then(|options| options.get("InterfaceVersion"));
+        if accuracy_threshold.is_some() && version.is_some_and(|v| v == "BLHA1") {
+            return Err(GoSamError::ProcessError(
+                "BLHA1 OLPs report no accuracy to compare with the accuracy threshold".to_owned(),
+            ))?;
+        }
         let gs_opts;
         if let Some(options) = gosam_options {
             let mut map = IndexMap::with_capacity(options.len());
@@ -231,41 +1193,147 @@ impl GoSamProcess {
         } else {
             gs_opts = None;
         }
-        Ok(GoSamProcess {
-            coupling_orders,
-            nlo_coupling,
-            contract_options: contract_opts,
-            gosam_options: gs_opts,
-            subprocesses: vec![],
-            model,
-            olp: None,
-        })
+        return Ok((
+            GoSamProcess {
+                coupling_orders,
+                contract_options: contract_opts,
+                gosam_options: gs_opts,
+            },
+            Process::new(model, nlo_coupling, accuracy_threshold, seed),
+        ));
     }
 
+    #[pyo3(signature = (incoming, outgoing, amplitude_type, coupling_powers = None))]
     fn add_subprocess(
-        &mut self,
+        mut slf: PyRefMut<'_, Self>,
         incoming: Vec<i64>,
         outgoing: Vec<i64>,
         amplitude_type: AmplitudeType,
-    ) {
-        let id = self.subprocesses.len() as i64;
-        self.subprocesses.push(Subprocess {
-            id,
-            incoming_pdg: incoming,
-            outgoing_pdg: outgoing,
-            amplitude_type,
-        });
+        coupling_powers: Option<IndexMap<String, usize>>,
+    ) -> PyResult<()> {
+        let subprocess = slf.subprocess(incoming, outgoing, amplitude_type, coupling_powers)?;
+        slf.as_super().subprocesses.push(subprocess);
+        return Ok(());
     }
 
-    fn setup(&mut self, py: Python<'_>) -> PyResult<()> {
-        py.allow_threads(|| -> Result<(), GoSamError> { self.setup_process() })?;
+    fn setup(mut slf: PyRefMut<'_, Self>, py: Python<'_>) -> PyResult<()> {
+        let gosam = GoSamProcess::clone(&slf);
+        let process: &mut Process = slf.as_super();
+        py.allow_threads(|| -> Result<(), GoSamError> { gosam.setup_process(process) })?;
         Ok(())
     }
+}
+
+#[pymethods]
+impl Process {
+    /// Seed of the process, from which all sampling calls without an explicit seed are derived
+    #[getter]
+    fn seed(&self) -> u64 {
+        return self.seed;
+    }
+
+    /// Seed used by the last sampling call
+    #[getter]
+    fn last_seed(&self) -> u64 {
+        return self.last_seed.load(Ordering::Relaxed);
+    }
+
+    /// Generation-level cuts applied to all sampled points
+    #[getter]
+    fn get_cuts(&self) -> Vec<Cut> {
+        return self.cuts.clone();
+    }
+
+    #[setter]
+    fn set_cuts(&mut self, cuts: Vec<Cut>) {
+        self.cuts = cuts;
+    }
+
+    /// Fraction of the points of the last sampling call that passed the cuts
+    #[getter(cut_efficiency)]
+    fn get_cut_efficiency(&self) -> f64 {
+        return self.cut_efficiency();
+    }
+
+    /// Rescue steps applied to points with an accuracy worse than the threshold
+    #[getter]
+    fn get_rescue(&self) -> Vec<Rescue> {
+        return self.rescue.clone();
+    }
+
+    #[setter]
+    fn set_rescue(&mut self, rescue: Vec<Rescue>) {
+        self.rescue = rescue;
+    }
+
+    /// Whether sampling calls discard points which are still unstable after all rescue steps
+    #[getter]
+    fn get_discard_unstable(&self) -> bool {
+        return self.discard_unstable;
+    }
+
+    #[setter]
+    fn set_discard_unstable(&mut self, discard_unstable: bool) {
+        self.discard_unstable = discard_unstable;
+    }
+
+    /// Number of unstable points discarded by the last sampling call
+    #[getter]
+    fn n_discarded(&self) -> u64 {
+        return self.n_discarded.load(Ordering::Relaxed);
+    }
+
+    /// Number of stable, rescued and unstable points of the last sampling call that passed the cuts
+    #[getter]
+    fn rescue_statistics(&self) -> IndexMap<&'static str, u64> {
+        return ["stable", "rescued", "unstable"]
+            .into_iter()
+            .zip(self.point_statistics.iter())
+            .map(|(status, n)| (status, n.load(Ordering::Relaxed)))
+            .collect();
+    }
+
+    /// Arithmetic precision in which sampling calls generate the phase-space points
+    #[getter]
+    fn get_precision(&self) -> Precision {
+        return self.precision;
+    }
+
+    #[setter]
+    fn set_precision(&mut self, precision: Precision) {
+        self.precision = precision;
+    }
+
+    /// Tolerance of the relative violations of momentum conservation and of the mass shells of points passed to
+    /// `eval`, `eval_batch` and `check_poles`, or `None` to evaluate points without checks
+    #[getter]
+    fn get_point_tolerance(&self) -> Option<f64> {
+        return self.point_tolerance;
+    }
+
+    #[setter]
+    fn set_point_tolerance(&mut self, point_tolerance: Option<f64>) {
+        self.point_tolerance = point_tolerance;
+    }
+
+    /// Whether points passed to `eval`, `eval_batch` and `check_poles` are projected onto the mass shells and exact
+    /// momentum conservation before the evaluation
+    #[getter]
+    fn get_project_points(&self) -> bool {
+        return self.project_points;
+    }
+
+    #[setter]
+    fn set_project_points(&mut self, project_points: bool) {
+        self.project_points = project_points;
+    }
 
     fn set_parameter(&mut self, parameter: String, real: f64, imag: f64) -> PyResult<()> {
         if let Some(ref olp) = self.olp {
             self.model.update_mass(&parameter, real);
-            return Ok(olp.set_parameter(&parameter, real, imag)?);
+            olp.set_parameter(&parameter, real, imag)?;
+            self.parameters.insert(parameter, (real, imag));
+            return Ok(());
         } else {
             return Err(GoSamError::UnintializedError("set_parameter".to_owned()))?;
         }
@@ -273,7 +1341,7 @@ impl GoSamProcess {
 
     fn print_parameters(&self, filename: String) -> PyResult<()> {
         if let Some(ref olp) = self.olp {
-            return Ok(olp.print_parameters(&filename));
+            return Ok(olp.print_parameters(&filename)?);
         } else {
             return Err(GoSamError::UnintializedError("print_parameters".to_owned()))?;
         }
@@ -285,49 +1353,238 @@ impl GoSamProcess {
         id: usize,
         scale: f64,
         vecs: Vec<[f64; 4]>,
-    ) -> PyResult<Vec<f64>> {
+    ) -> PyResult<TypedResult> {
+        self.check_id(id)?;
         if let Some(ref olp) = self.olp {
-            return Ok(py.allow_threads(|| -> Result<_, _> { olp.eval(id, &vecs, scale) })?);
+            let vecs = self.prepare_point(id, &vecs)?;
+            let res = py.allow_threads(|| -> Result<_, _> {
+                self.eval_point(olp.as_ref(), id, &vecs, scale)
+            })?;
+            return Ok(self.typed(id, res));
         } else {
             return Err(GoSamError::UnintializedError("eval".to_owned()))?;
         }
     }
 
-    #[pyo3(signature = (id, s, scale = None))]
+    /// Evaluate subprocess `id` at the point `vecs` in each of its channels separately
+    fn eval_channels(
+        &self,
+        py: Python<'_>,
+        id: usize,
+        scale: f64,
+        vecs: Vec<[f64; 4]>,
+    ) -> PyResult<Vec<TypedResult>> {
+        self.check_id(id)?;
+        let lib = self.get_olp("eval_channels")?;
+        let vecs = self.prepare_point(id, &vecs)?;
+        let channels = py.allow_threads(|| -> Result<_, _> {
+            self.eval_point_channels(lib, id, &vecs, scale)
+        })?;
+        return Ok(channels
+            .into_iter()
+            .map(|res| self.typed(id, res))
+            .collect());
+    }
+
+    /// OLP ids of the channels of subprocess `id` assigned in the contract
+    fn channels(&self, id: usize) -> PyResult<Vec<i64>> {
+        self.check_id(id)?;
+        self.get_olp("channels")?;
+        return Ok(self.olp_ids[id].clone());
+    }
+
+    /// Coupling powers in effect when subprocess `id` was declared in the contract
+    fn coupling_powers(&self, id: usize) -> PyResult<IndexMap<String, i64>> {
+        self.check_id(id)?;
+        let lib = self.get_olp("coupling_powers")?;
+        return Ok(lib.subprocess(self.olp_ids[id][0]).coupling_powers.clone());
+    }
+
+    /// Options other than the amplitude type and coupling powers in effect when subprocess `id` was declared in the
+    /// contract
+    fn subprocess_options(&self, id: usize) -> PyResult<IndexMap<String, String>> {
+        self.check_id(id)?;
+        let lib = self.get_olp("subprocess_options")?;
+        return Ok(lib.subprocess(self.olp_ids[id][0]).options.clone());
+    }
+
+    #[pyo3(signature = (id, s, scale = None, seed = None, frame = None))]
     fn eval_random(
         &self,
         py: Python<'_>,
         id: usize,
         s: PyScale,
         scale: Option<f64>,
-    ) -> PyResult<(Vec<[f64; 4]>, Vec<f64>)> {
-        let lib;
-        if let Some(ref olp) = self.olp {
-            lib = olp;
-        } else {
-            return Err(GoSamError::UnintializedError("eval".to_owned()))?;
-        }
-        let masses = self.subprocesses[id]
-            .incoming_pdg
-            .iter()
-            .chain(self.subprocesses[id].outgoing_pdg.iter())
-            .map(|i| self.model.get_mass(*i))
-            .collect::<Vec<_>>();
-        let n_in = self.subprocesses[id].incoming_pdg.len();
-        let mut rng = fastrand::Rng::new();
+        seed: Option<u64>,
+        frame: Option<Frame>,
+    ) -> PyResult<(Vec<[f64; 4]>, TypedResult, f64)> {
+        self.check_id(id)?;
+        let lib = self.get_olp("eval_random")?;
+        self.check_scale(id, &s)?;
+        self.check_grid(id, &s)?;
+        self.check_frame(id, &s, frame.as_ref())?;
+        let masses = self.masses(id);
+        let mut rng = substream(self.call_seed(seed), 0);
 
         let result = py.allow_threads(|| -> PyResult<_> {
-            let (mut renorm_scale, vecs) = rambo((&s).into(), &masses, n_in, &mut rng);
+            let (mut renorm_scale, vecs, weight, _) =
+                self.generate_accepted(id, &s, frame.as_ref(), &masses, &mut rng)?;
             if let Some(scale) = scale {
                 renorm_scale = scale;
             }
-            let vals = lib.eval(id, &vecs, renorm_scale)?;
-            return Ok((vecs, vals));
+            let vals = self.eval_point(lib, id, &vecs, renorm_scale)?;
+            return Ok((vecs, self.typed(id, vals), weight));
         });
         return result;
     }
 
-    #[pyo3(signature = (id, s, n_points, scale = None))]
+    /// Check the numerical health of the process library for subprocess `id` at `n_points` random points: the
+    /// results have to be invariant under random rotations and boosts, and the poles of `Loop` amplitudes have to
+    /// match the infrared structure of QCD while those of `LoopInduced` amplitudes have to vanish
+    #[pyo3(signature = (id, s, n_points = 10, scale = None, alpha_s = None, seed = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn validate(
+        &self,
+        py: Python<'_>,
+        id: usize,
+        s: PyScale,
+        n_points: usize,
+        scale: Option<f64>,
+        alpha_s: Option<f64>,
+        seed: Option<u64>,
+    ) -> PyResult<ValidationResult> {
+        self.check_id(id)?;
+        let lib = self.get_olp("validate")?;
+        let subprocess = &self.subprocesses[id];
+        let amplitude_type = subprocess.amplitude_type;
+        if matches!(
+            amplitude_type,
+            AmplitudeType::scTree | AmplitudeType::scTree2
+        ) {
+            return Err(GoSamError::ValidationError(format!(
+                "Spin-correlated amplitudes of subprocess {id} are not Lorentz invariant"
+            )))?;
+        }
+        self.check_scale(id, &s)?;
+        self.check_grid(id, &s)?;
+        let masses = self.masses(id);
+        let alpha_s = alpha_s.or_else(|| self.alpha_s());
+        let legs = match (amplitude_type, alpha_s) {
+            (AmplitudeType::Loop, Some(_)) => self.coloured_legs(id),
+            _ => None,
+        };
+        let single_pole_known = legs
+            .as_ref()
+            .is_some_and(|legs| self.correlators_known(id, legs));
+        let crossed = self.crossed_subprocess(id);
+        let mut result = match amplitude_type {
+            AmplitudeType::LoopInduced => ValidationResult::new(crossed.is_some(), true, true),
+            _ => ValidationResult::new(crossed.is_some(), legs.is_some(), single_pole_known),
+        };
+        let mut rng = substream(self.call_seed(seed), 0);
+
+        py.allow_threads(|| -> PyResult<_> {
+            for _ in 0..n_points {
+                let (mut renorm_scale, vecs, _, _) =
+                    self.generate_accepted(id, &s, None, &masses, &mut rng)?;
+                if let Some(scale) = scale {
+                    renorm_scale = scale;
+                }
+                let res = self.eval_point(lib, id, &vecs, renorm_scale)?;
+                let rotated = random_rotation(&vecs, &mut rng);
+                let rotated = self.eval_point(lib, id, &rotated, renorm_scale)?;
+                let boosted = random_boost(&vecs, &mut rng);
+                let boosted = self.eval_point(lib, id, &boosted, renorm_scale)?;
+                result.rotation = result.rotation.min(agreement(&res.values, &rotated.values));
+                result.boost = result.boost.min(agreement(&res.values, &boosted.values));
+                if let Some(crossed) = crossed {
+                    let mut exchanged = vecs.clone();
+                    exchanged.swap(0, 1);
+                    let exchanged = self.eval_point(lib, crossed, &exchanged, renorm_scale)?;
+                    result.crossing = result
+                        .crossing
+                        .map(|d| d.min(agreement(&res.values, &exchanged.values)));
+                }
+                let (double_pole_digits, single_pole_digits) = match (amplitude_type, &legs) {
+                    (AmplitudeType::LoopInduced, _) => (
+                        suppression(res.values[0], res.values[2]),
+                        suppression(res.values[1], res.values[2]),
+                    ),
+                    (AmplitudeType::Loop, Some(legs)) => {
+                        let (double_pole, single_pole) = self.predicted_poles(
+                            lib,
+                            id,
+                            &vecs,
+                            renorm_scale,
+                            res.values[3],
+                            legs,
+                            alpha_s.unwrap(),
+                        )?;
+                        (
+                            digits(res.values[0], double_pole),
+                            single_pole.map_or(0., |s| digits(res.values[1], s)),
+                        )
+                    }
+                    _ => (0., 0.),
+                };
+                result.double_pole = result.double_pole.map(|d| d.min(double_pole_digits));
+                result.single_pole = result.single_pole.map(|d| d.min(single_pole_digits));
+                result.n_points += 1;
+            }
+            return Ok(());
+        })?;
+        return Ok(result);
+    }
+
+    /// Compare the poles of the `Loop` subprocess `id` at the point `vecs` with the prediction of the I-operator
+    #[pyo3(signature = (id, scale, vecs, alpha_s = None))]
+    fn check_poles(
+        &self,
+        py: Python<'_>,
+        id: usize,
+        scale: f64,
+        vecs: Vec<[f64; 4]>,
+        alpha_s: Option<f64>,
+    ) -> PyResult<PoleCheck> {
+        self.check_id(id)?;
+        let lib = self.get_olp("check_poles")?;
+        if self.subprocesses[id].amplitude_type != AmplitudeType::Loop {
+            return Err(GoSamError::ValidationError(format!(
+                "Subprocess {id} is not a Loop amplitude"
+            )))?;
+        }
+        let Some(alpha_s) = alpha_s.or_else(|| self.alpha_s()) else {
+            return Err(GoSamError::ValidationError(
+                "Unknown strong coupling, pass alpha_s or set it with set_parameter".to_owned(),
+            ))?;
+        };
+        let Some(legs) = self.coloured_legs(id) else {
+            return Err(GoSamError::ValidationError(format!(
+                "The poles of subprocess {id} are only known for QCD corrections with massless coloured particles"
+            )))?;
+        };
+        if !self.correlators_known(id, &legs) {
+            return Err(GoSamError::ValidationError(format!(
+                "Subprocess {id} has more than three coloured particles and no matching ccTree subprocess"
+            )))?;
+        }
+        let vecs = self.prepare_point(id, &vecs)?;
+        let result = py.allow_threads(|| -> Result<_, BLHAError> {
+            let res = self.eval_point(lib, id, &vecs, scale)?;
+            let (double_pole, single_pole) =
+                self.predicted_poles(lib, id, &vecs, scale, res.values[3], &legs, alpha_s)?;
+            return Ok(PoleCheck {
+                double_pole: res.values[0],
+                single_pole: res.values[1],
+                expected_double_pole: double_pole,
+                expected_single_pole: single_pole.unwrap(),
+            });
+        })?;
+        return Ok(result);
+    }
+
+    #[pyo3(signature = (id, s, n_points, scale = None, seed = None, n_threads = None, frame = None))]
+    #[allow(clippy::too_many_arguments)]
     fn sample(
         &self,
         py: Python<'_>,
@@ -335,56 +1592,1042 @@ impl GoSamProcess {
         s: PyScale,
         n_points: usize,
         scale: Option<f64>,
-    ) -> PyResult<Vec<(Vec<[f64; 4]>, Vec<f64>)>> {
-        let lib;
-        if let Some(ref olp) = self.olp {
-            lib = olp;
+        seed: Option<u64>,
+        n_threads: Option<usize>,
+        frame: Option<Frame>,
+    ) -> PyResult<Vec<SamplePoint>> {
+        self.check_id(id)?;
+        let mut result = Vec::with_capacity(n_points);
+        let settings = SampleSettings {
+            s: &s,
+            frame: frame.as_ref(),
+            scale,
+            seed,
+            n_threads,
+            progress: true,
+        };
+        let efficiency = self.sample_with(py, id, n_points, settings, |vecs, weight, point| {
+            if let Sampled::Evaluated(vals) = point {
+                result.push((vecs.to_vec(), self.typed(id, vals), weight));
+            }
+        })?;
+        result
+            .iter_mut()
+            .for_each(|(_, _, weight)| *weight *= efficiency);
+        return Ok(result);
+    }
+
+    /// Sample `n_points` points of the `1 -> n` subprocess `id` in the rest frame of the decaying particle
+    #[pyo3(signature = (id, n_points, scale = None, seed = None, n_threads = None))]
+    fn sample_decay(
+        &self,
+        py: Python<'_>,
+        id: usize,
+        n_points: usize,
+        scale: Option<f64>,
+        seed: Option<u64>,
+        n_threads: Option<usize>,
+    ) -> PyResult<Vec<SamplePoint>> {
+        self.check_id(id)?;
+        if self.subprocesses[id].incoming_pdg.len() != 1 {
+            return Err(GoSamError::ScaleError(format!(
+                "Subprocess {id} is not a decay"
+            )))?;
+        }
+        let m = self.masses(id)[0];
+        return self.sample(
+            py,
+            id,
+            PyScale::Fixed(m * m),
+            n_points,
+            scale,
+            seed,
+            n_threads,
+            None,
+        );
+    }
+
+    /// Invariants `s_ij` of all pairs of legs of subprocess `id` for a batch of `N` phase-space points
+    fn mandelstam<'py>(
+        &self,
+        py: Python<'py>,
+        id: usize,
+        momenta: PyReadonlyArray3<'py, f64>,
+    ) -> PyResult<Bound<'py, PyArray3<f64>>> {
+        self.check_id(id)?;
+        let momenta = momenta.as_array();
+        let subprocess = &self.subprocesses[id];
+        let n_legs = subprocess.n_legs();
+        let n_points = momenta.shape()[0];
+        if momenta.shape()[1..] != [n_legs, 4] {
+            return Err(GoSamError::ShapeError(format!(
+                "momenta must have shape (N, {n_legs}, 4), got {:?}",
+                momenta.shape()
+            )))?;
+        }
+        let mut result = Vec::with_capacity(n_points * n_legs * n_legs);
+        let mut vecs = vec![[0.; 4]; n_legs];
+        for point in momenta.outer_iter() {
+            for (v, p) in vecs.iter_mut().zip(point.outer_iter()) {
+                for mu in 0..=3 {
+                    v[mu] = p[mu];
+                }
+            }
+            result.extend(invariants(&vecs, subprocess.incoming_pdg.len()));
+        }
+        return PyArray1::from_vec(py, result).reshape([n_points, n_legs, n_legs]);
+    }
+
+    /// Relative violation of momentum conservation and of the on-shell conditions of each point of `momenta` with
+    /// shape `(N, n_legs, 4)` for subprocess `id`, computed from the momenta in double precision
+    fn momentum_violation<'py>(
+        &self,
+        py: Python<'py>,
+        id: usize,
+        momenta: PyReadonlyArray3<'py, f64>,
+    ) -> PyResult<ViolationArrays<'py>> {
+        self.check_id(id)?;
+        let momenta = momenta.as_array();
+        let subprocess = &self.subprocesses[id];
+        let n_legs = subprocess.n_legs();
+        if momenta.shape()[1..] != [n_legs, 4] {
+            return Err(GoSamError::ShapeError(format!(
+                "momenta must have shape (N, {n_legs}, 4), got {:?}",
+                momenta.shape()
+            )))?;
+        }
+        let masses = self.masses(id);
+        let n_in = subprocess.incoming_pdg.len();
+        let mut conservation = Vec::with_capacity(momenta.shape()[0]);
+        let mut on_shell = Vec::with_capacity(momenta.shape()[0]);
+        let mut vecs = vec![[0.; 4]; n_legs];
+        for point in momenta.outer_iter() {
+            for (v, p) in vecs.iter_mut().zip(point.outer_iter()) {
+                for mu in 0..=3 {
+                    v[mu] = p[mu];
+                }
+            }
+            let violation = momentum_violation(&vecs, &masses, n_in);
+            conservation.push(violation.0);
+            on_shell.push(violation.1);
+        }
+        return Ok((
+            PyArray1::from_vec(py, conservation),
+            PyArray1::from_vec(py, on_shell),
+        ));
+    }
+
+    /// Generate `n_points` points of subprocess `id` without evaluating them, together with their violations
+    /// computed in the precision of the generation before the momenta are rounded to `f64`
+    #[pyo3(signature = (id, s, n_points, seed = None))]
+    fn sample_points<'py>(
+        &self,
+        py: Python<'py>,
+        id: usize,
+        s: PyScale,
+        n_points: usize,
+        seed: Option<u64>,
+    ) -> PyResult<PointArrays<'py>> {
+        self.check_id(id)?;
+        self.check_scale(id, &s)?;
+        self.check_grid(id, &s)?;
+        let seed = self.call_seed(seed);
+        let masses = self.masses(id);
+        let n_legs = self.subprocesses[id].n_legs();
+        let mut momenta = Vec::with_capacity(n_points * n_legs * 4);
+        let mut weights = Vec::with_capacity(n_points);
+        let mut conservation = Vec::with_capacity(n_points);
+        let mut on_shell = Vec::with_capacity(n_points);
+        py.allow_threads(|| {
+            // Same substreams as `sample_with`. A frame draws from the same streams, so the points only agree with
+            // those of `sample_array` if it is called without a frame
+            for chunk in 0..n_points.div_ceil(CHUNK_SIZE) {
+                let mut rng = substream(seed, chunk as u64);
+                for _ in (chunk * CHUNK_SIZE)..((chunk + 1) * CHUNK_SIZE).min(n_points) {
+                    let (_, vecs, weight, violation) = self.generate(id, &s, &masses, &mut rng);
+                    momenta.extend(vecs.iter().flatten());
+                    weights.push(weight);
+                    conservation.push(violation[0]);
+                    on_shell.push(violation[1]);
+                }
+            }
+        });
+        return Ok((
+            PyArray1::from_vec(py, momenta).reshape([n_points, n_legs, 4])?,
+            PyArray1::from_vec(py, weights),
+            PyArray1::from_vec(py, conservation),
+            PyArray1::from_vec(py, on_shell),
+        ));
+    }
+
+    fn eval_batch<'py>(
+        &self,
+        py: Python<'py>,
+        id: usize,
+        momenta: PyReadonlyArray3<'py, f64>,
+        scales: PyReadonlyArray1<'py, f64>,
+    ) -> PyResult<BatchArrays<'py>> {
+        self.check_id(id)?;
+        let lib = self.get_olp("eval_batch")?;
+        let momenta = momenta.as_array();
+        let scales = scales.as_array();
+        let n_legs = self.subprocesses[id].n_legs();
+        let n_points = momenta.shape()[0];
+        if momenta.shape()[1..] != [n_legs, 4] {
+            return Err(GoSamError::ShapeError(format!(
+                "momenta must have shape (N, {n_legs}, 4), got {:?}",
+                momenta.shape()
+            )))?;
+        }
+        if scales.len() != n_points {
+            return Err(GoSamError::ShapeError(format!(
+                "scales must have shape ({n_points},), got {:?}",
+                scales.shape()
+            )))?;
+        }
+        let n_results = lib.n_results(self.olp_ids[id][0]);
+        let mut results = Vec::with_capacity(n_points * n_results);
+        let mut accuracies = Vec::with_capacity(n_points);
+        py.allow_threads(|| -> Result<(), GoSamError> {
+            let mut vecs = vec![[0.; 4]; n_legs];
+            for (i, (point, scale)) in momenta.outer_iter().zip(scales.iter()).enumerate() {
+                for (v, p) in vecs.iter_mut().zip(point.outer_iter()) {
+                    for mu in 0..=3 {
+                        v[mu] = p[mu];
+                    }
+                }
+                let vecs = self.prepare_point(id, &vecs).map_err(|err| match err {
+                    GoSamError::PointError(msg) => {
+                        GoSamError::PointError(format!("row {i}: {msg}"))
+                    }
+                    err => err,
+                })?;
+                let res = self.eval_point(lib, id, &vecs, *scale)?;
+                results.extend_from_slice(&res.values);
+                accuracies.push(res.accuracy);
+            }
+            return Ok(());
+        })?;
+        return Ok((
+            PyArray1::from_vec(py, results).reshape([n_points, n_results])?,
+            PyArray1::from_vec(py, accuracies),
+        ));
+    }
+
+    #[pyo3(signature = (id, s, n_points, scale = None, seed = None, n_threads = None, frame = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn sample_array<'py>(
+        &self,
+        py: Python<'py>,
+        id: usize,
+        s: PyScale,
+        n_points: usize,
+        scale: Option<f64>,
+        seed: Option<u64>,
+        n_threads: Option<usize>,
+        frame: Option<Frame>,
+    ) -> PyResult<SampleArrays<'py>> {
+        self.check_id(id)?;
+        let n_legs = self.subprocesses[id].n_legs();
+        let n_results = self.get_olp("sample_array")?.n_results(self.olp_ids[id][0]);
+        let mut momenta = Vec::with_capacity(n_points * n_legs * 4);
+        let mut results = Vec::with_capacity(n_points * n_results);
+        let mut accuracies = Vec::with_capacity(n_points);
+        let mut weights = Vec::with_capacity(n_points);
+        let settings = SampleSettings {
+            s: &s,
+            frame: frame.as_ref(),
+            scale,
+            seed,
+            n_threads,
+            progress: true,
+        };
+        let efficiency = self.sample_with(py, id, n_points, settings, |vecs, weight, point| {
+            if let Sampled::Evaluated(vals) = point {
+                momenta.extend(vecs.iter().flatten());
+                results.extend_from_slice(&vals.values);
+                accuracies.push(vals.accuracy);
+                weights.push(weight);
+            }
+        })?;
+        weights.iter_mut().for_each(|weight| *weight *= efficiency);
+        let n_accepted = weights.len();
+        return Ok((
+            PyArray1::from_vec(py, momenta).reshape([n_accepted, n_legs, 4])?,
+            PyArray1::from_vec(py, results).reshape([n_accepted, n_results])?,
+            PyArray1::from_vec(py, accuracies),
+            PyArray1::from_vec(py, weights),
+        ));
+    }
+
+    /// Integrate the cross section (or decay width) of subprocess `id` at center of mass energy squared `s`, where `s`
+    /// is either a fixed partonic energy or a `Scale`. The flux factor is evaluated at the partonic energy of each point.
+    #[pyo3(signature = (id, s, n_points, component = None, scale = None, target_error = None, symmetry_factor = false, average = false, seed = None, n_threads = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn integrate(
+        &self,
+        py: Python<'_>,
+        id: usize,
+        s: ScaleArg,
+        n_points: usize,
+        component: Option<Component>,
+        scale: Option<f64>,
+        target_error: Option<f64>,
+        symmetry_factor: bool,
+        average: bool,
+        seed: Option<u64>,
+        n_threads: Option<usize>,
+    ) -> PyResult<IntegrationResult> {
+        self.check_id(id)?;
+        let component = self.component(id, component)?;
+        let scale_type = match s {
+            ScaleArg::Fixed(s) => PyScale::Fixed(s),
+            ScaleArg::Scale(s) => s,
+        };
+        let n_in = self.subprocesses[id].incoming_pdg.len();
+        let mut acc = Accumulator::default();
+        let mut history = Vec::new();
+        let add = |acc: &mut Accumulator,
+                   history: &mut Vec<(usize, f64, f64)>,
+                   vecs: &[[f64; 4]],
+                   weight: f64,
+                   point: Sampled| {
+            match point {
+                Sampled::Evaluated(vals) => {
+                    let p: [f64; 4] =
+                        std::array::from_fn(|mu| vecs[..n_in].iter().map(|p| p[mu]).sum());
+                    let factor = self.normalization(id, scalar(&p, &p), symmetry_factor, average);
+                    acc.add(factor * weight * component.select(&vals.values));
+                }
+                Sampled::Cut => acc.add(0.),
+                // Discarded points are excluded from the estimate instead of counting as zero
+                Sampled::Discarded(_) => return,
+            }
+            if acc.n().is_multiple_of(CHUNK_SIZE) {
+                history.push((acc.n(), acc.mean(), acc.error()));
+            }
+        };
+        if let Some(target_error) = target_error {
+            let seed = self.call_seed(seed);
+            let mut n = 0;
+            let mut batch = 0;
+            while n < n_points {
+                let n_batch = (n_points - n).min(INTEGRATION_BATCH_SIZE);
+                let settings = SampleSettings {
+                    s: &scale_type,
+                    frame: None,
+                    scale,
+                    seed: Some(substream(seed, batch).u64(..)),
+                    n_threads,
+                    progress: false,
+                };
+                self.sample_with(py, id, n_batch, settings, |vecs, weight, point| {
+                    add(&mut acc, &mut history, vecs, weight, point)
+                })?;
+                n += n_batch;
+                batch += 1;
+                if (acc.error() / acc.mean()).abs() < target_error {
+                    break;
+                }
+            }
+            self.last_seed.store(seed, Ordering::Relaxed);
         } else {
-            return Err(GoSamError::UnintializedError("eval".to_owned()))?;
+            let settings = SampleSettings {
+                s: &scale_type,
+                frame: None,
+                scale,
+                seed,
+                n_threads,
+                progress: true,
+            };
+            self.sample_with(py, id, n_points, settings, |vecs, weight, point| {
+                add(&mut acc, &mut history, vecs, weight, point)
+            })?;
+        }
+        if history.last().map(|h| h.0) != Some(acc.n()) {
+            history.push((acc.n(), acc.mean(), acc.error()));
         }
-        let masses = self.subprocesses[id]
+        return Ok(IntegrationResult::new(&acc, history));
+    }
+
+    /// Train a VEGAS grid for subprocess `id` on the phase-space weight times the selected component of the result
+    #[pyo3(signature = (id, s, n_iterations, n_points, component = None, scale = None, n_bins = 50, alpha = 1.5, seed = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn train_vegas(
+        &mut self,
+        py: Python<'_>,
+        id: usize,
+        s: PyScale,
+        n_iterations: usize,
+        n_points: usize,
+        component: Option<Component>,
+        scale: Option<f64>,
+        n_bins: usize,
+        alpha: f64,
+        seed: Option<u64>,
+    ) -> PyResult<Vec<IntegrationResult>> {
+        self.check_id(id)?;
+        let lib = self.get_olp("train_vegas")?;
+        self.check_scale(id, &s)?;
+        self.check_grid(id, &s)?;
+        let component = self.component(id, component)?;
+        let seed = self.call_seed(seed);
+        let masses = self.masses(id);
+        let pdg_in = &self.subprocesses[id].incoming_pdg;
+        let mut grid = match self.vegas_grids.get(&id) {
+            Some(grid) => grid.clone(),
+            None => VegasGrid::new(
+                s.dimension(self.subprocesses[id].outgoing_pdg.len()),
+                n_bins,
+            ),
+        };
+        let results = py.allow_threads(|| -> PyResult<_> {
+            let mut results = Vec::with_capacity(n_iterations);
+            let mut u = vec![0.; grid.dim()];
+            let mut x = vec![0.; grid.dim()];
+            for iteration in 0..n_iterations {
+                let mut rng = substream(seed, iteration as u64);
+                let mut acc = Accumulator::default();
+                let mut vegas_acc = VegasAccumulator::new(&grid);
+                for _ in 0..n_points {
+                    u.iter_mut().for_each(|u| *u = rng.f64());
+                    let jacobian = grid.map(&u, &mut x);
+                    let (mut renorm_scale, vecs, weight, _) =
+                        s.generate(pdg_in, &masses, self.precision, &mut Hypercube::new(&x));
+                    if !self.passes_cuts(id, &vecs)? {
+                        acc.add(0.);
+                        continue;
+                    }
+                    if let Some(scale) = scale {
+                        renorm_scale = scale;
+                    }
+                    let vals = self.eval_point(lib, id, &vecs, renorm_scale)?;
+                    let f = jacobian * weight * component.select(&vals.values);
+                    acc.add(f);
+                    vegas_acc.add(&u, f);
+                }
+                results.push(IntegrationResult::new(&acc, vec![]));
+                grid.refine(&vegas_acc, alpha);
+            }
+            return Ok(results);
+        })?;
+        self.vegas_grids.insert(id, grid);
+        return Ok(results);
+    }
+
+    fn save_vegas_grid(&self, id: usize, path: PathBuf) -> PyResult<()> {
+        self.check_id(id)?;
+        match self.vegas_grids.get(&id) {
+            Some(grid) => Ok(grid.save(&path)?),
+            None => Err(GoSamError::GridError(format!(
+                "Subprocess {id} has no VEGAS grid"
+            )))?,
+        }
+    }
+
+    fn load_vegas_grid(&mut self, id: usize, path: PathBuf) -> PyResult<()> {
+        self.check_id(id)?;
+        self.vegas_grids.insert(id, VegasGrid::load(&path)?);
+        return Ok(());
+    }
+
+    fn clear_vegas_grid(&mut self, id: usize) {
+        self.vegas_grids.shift_remove(&id);
+    }
+
+    /// Generate unweighted events of subprocess `id` by hit-or-miss against the maximum weight of the warm-up
+    #[pyo3(signature = (id, s, n_events, component = None, scale = None, safety_factor = 1.0, n_warmup = 1000, max_points = None, seed = None, n_threads = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn unweighted_events(
+        &self,
+        py: Python<'_>,
+        id: usize,
+        s: PyScale,
+        n_events: usize,
+        component: Option<Component>,
+        scale: Option<f64>,
+        safety_factor: f64,
+        n_warmup: usize,
+        max_points: Option<usize>,
+        seed: Option<u64>,
+        n_threads: Option<usize>,
+    ) -> PyResult<UnweightedEvents> {
+        self.check_id(id)?;
+        let component = self.component(id, component)?;
+        let seed = self.call_seed(seed);
+        let settings = |batch: u64, progress: bool| SampleSettings {
+            s: &s,
+            frame: None,
+            scale,
+            seed: Some(substream(seed, batch).u64(..)),
+            n_threads,
+            progress,
+        };
+        let mut max_weight: f64 = 0.;
+        self.sample_with(py, id, n_warmup, settings(0, false), |_, weight, point| {
+            if let Sampled::Evaluated(vals) = point {
+                max_weight = max_weight.max((weight * component.select(&vals.values)).abs());
+            }
+        })?;
+        if max_weight <= 0. || max_weight.is_nan() {
+            return Err(GoSamError::IntegrandError(format!(
+                "All {n_warmup} warm-up points of subprocess {id} have vanishing weight"
+            )))?;
+        }
+        let mut unweighter = Unweighter::new(max_weight, safety_factor, substream(seed, 1));
+        let mut acc = Accumulator::default();
+        let mut momenta = Vec::with_capacity(n_events);
+        let mut weights = Vec::with_capacity(n_events);
+        let mut batch = 2;
+        while unweighter.n_accepted() < n_events
+            && max_points.is_none_or(|max_points| acc.n() < max_points)
+        {
+            let mut n_batch = unweighter
+                .trials_needed(n_events)
+                .map_or(INTEGRATION_BATCH_SIZE, |n| {
+                    n.clamp(1, INTEGRATION_BATCH_SIZE)
+                });
+            if let Some(max_points) = max_points {
+                n_batch = n_batch.min(max_points - acc.n());
+            }
+            self.sample_with(
+                py,
+                id,
+                n_batch,
+                settings(batch, false),
+                |vecs, weight, point| {
+                    let vals = match point {
+                        Sampled::Evaluated(vals) => vals,
+                        Sampled::Cut => {
+                            acc.add(0.);
+                            return;
+                        }
+                        Sampled::Discarded(_) => return,
+                    };
+                    let w = weight * component.select(&vals.values);
+                    acc.add(w);
+                    if unweighter.n_accepted() < n_events
+                        && let Some(event_weight) = unweighter.trial(w)
+                    {
+                        momenta.push(vecs.to_vec());
+                        weights.push(event_weight);
+                    }
+                },
+            )?;
+            batch += 1;
+        }
+        self.last_seed.store(seed, Ordering::Relaxed);
+        return Ok(UnweightedEvents::new(
+            momenta,
+            weights,
+            &unweighter,
+            acc.mean(),
+            acc.error(),
+        ));
+    }
+
+    /// Write the events `momenta` of subprocess `id` with weights `weights` to the Les Houches Event file `path`. The
+    /// beams are taken from `collider` if given, otherwise from the incoming partons of the first event.
+    #[pyo3(signature = (id, path, momenta, weights, scales = None, weight_names = None, cross_section = None, cross_section_error = None, unweighted = false, collider = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn write_lhe(
+        &self,
+        id: usize,
+        path: PathBuf,
+        momenta: Vec<Vec<[f64; 4]>>,
+        weights: EventWeights,
+        scales: Option<EventScales>,
+        weight_names: Option<Vec<String>>,
+        cross_section: Option<f64>,
+        cross_section_error: Option<f64>,
+        unweighted: bool,
+        collider: Option<Collider>,
+    ) -> PyResult<()> {
+        self.check_id(id)?;
+        let subprocess = &self.subprocesses[id];
+        let n_in = subprocess.incoming_pdg.len();
+        if weights.len() != momenta.len() {
+            return Err(GoSamError::ShapeError(format!(
+                "Got {} weights for {} events",
+                weights.len(),
+                momenta.len()
+            )))?;
+        }
+        if let Some(vecs) = momenta
+            .iter()
+            .find(|vecs| vecs.len() != subprocess.n_legs())
+        {
+            return Err(GoSamError::ShapeError(format!(
+                "Got event with {} momenta for subprocess {id} with {} legs",
+                vecs.len(),
+                subprocess.n_legs()
+            )))?;
+        }
+        if let Some(EventScales::PerEvent(scales)) = &scales
+            && scales.len() != momenta.len()
+        {
+            return Err(GoSamError::ShapeError(format!(
+                "Got {} scales for {} events",
+                scales.len(),
+                momenta.len()
+            )))?;
+        }
+        let weight_names = match weight_names {
+            Some(names) => names,
+            None if matches!(weights, EventWeights::Multiple(_)) => {
+                (0..weights.get(0).len()).map(|i| format!("w{i}")).collect()
+            }
+            None => Vec::new(),
+        };
+        if (0..weights.len()).any(|i| {
+            let n = weights.get(i).len();
+            n == 0 || (!weight_names.is_empty() && n != weight_names.len())
+        }) {
+            return Err(GoSamError::ShapeError(format!(
+                "Expected {} weights per event",
+                weight_names.len().max(1)
+            )))?;
+        }
+
+        let mut acc = Accumulator::default();
+        let mut max_weight: f64 = 0.;
+        for i in 0..weights.len() {
+            acc.add(weights.get(i)[0]);
+            max_weight = max_weight.max(weights.get(i)[0].abs());
+        }
+        // The mean of unweighted events is not the cross section
+        let (cross_section, cross_section_error) = match (unweighted, cross_section) {
+            (true, None) => {
+                return Err(GoSamError::ProcessError(
+                    "The cross section is required for unweighted events".to_owned(),
+                ))?;
+            }
+            (true, Some(xs)) => (xs, cross_section_error.unwrap_or(0.)),
+            (false, xs) => (
+                xs.unwrap_or(acc.mean()),
+                cross_section_error.unwrap_or(acc.error()),
+            ),
+        };
+        let (beam_pdg, beam_energies) = match collider {
+            Some(_) if n_in != 2 => {
+                return Err(GoSamError::ProcessError(format!(
+                    "Subprocess {id} has {n_in} incoming particles, a collider requires two"
+                )))?;
+            }
+            Some(collider) => {
+                let [beam1, beam2] = collider.beams();
+                (
+                    [
+                        beam1.pdg_id(subprocess.incoming_pdg[0]),
+                        beam2.pdg_id(subprocess.incoming_pdg[1]),
+                    ],
+                    [beam1.energy, beam2.energy],
+                )
+            }
+            None => {
+                let mut beam_pdg = [0; 2];
+                beam_pdg[..n_in].copy_from_slice(&subprocess.incoming_pdg);
+                let mut beam_energies = [0.; 2];
+                if let Some(vecs) = momenta.first() {
+                    for (e, p) in beam_energies.iter_mut().zip(vecs[..n_in].iter()) {
+                        *e = p[0];
+                    }
+                }
+                (beam_pdg, beam_energies)
+            }
+        };
+        let masses = self.masses(id);
+        let colours = subprocess
             .incoming_pdg
             .iter()
-            .chain(self.subprocesses[id].outgoing_pdg.iter())
-            .map(|i| self.model.get_mass(*i))
+            .chain(subprocess.outgoing_pdg.iter())
+            .map(|pdg| self.model.color(*pdg))
             .collect::<Vec<_>>();
-        let n_in = self.subprocesses[id].incoming_pdg.len();
-        let mut rng = fastrand::Rng::new();
+        let Some(colours) = colour_flow(&colours, n_in) else {
+            return Err(GoSamError::ProcessError(format!(
+                "No leading-colour flow for the colour representations {colours:?} of subprocess {id}"
+            )))?;
+        };
+        let init = LHEInit {
+            incoming_pdg: &subprocess.incoming_pdg,
+            outgoing_pdg: &subprocess.outgoing_pdg,
+            masses: &masses,
+            colours: &colours,
+            beam_pdg,
+            beam_energies,
+            cross_section,
+            cross_section_error,
+            max_weight,
+            unweighted,
+            weight_names: &weight_names,
+        };
+        let file = std::io::BufWriter::new(std::fs::File::create(&path)?);
+        let mut writer = LHEWriter::new(file, init)?;
+        for (i, vecs) in momenta.iter().enumerate() {
+            let scale = match &scales {
+                Some(EventScales::Fixed(scale)) => *scale,
+                Some(EventScales::PerEvent(scales)) => scales[i],
+                None => {
+                    let p: [f64; 4] =
+                        std::array::from_fn(|mu| vecs[..n_in].iter().map(|p| p[mu]).sum());
+                    scalar(&p, &p).abs().sqrt()
+                }
+            };
+            writer.write_event(vecs, weights.get(i), scale)?;
+        }
+        writer.finish()?;
+        return Ok(());
+    }
+}
 
-        let tqdm = match py.import("tqdm.auto") {
-            Ok(m) => Some(
-                m.getattr("tqdm")?
-                    .call((), Some(&[("total", n_points)].into_py_dict(py)?))?
-                    .unbind(),
-            ),
-            Err(_) => None,
+/// Process bound to an existing BLHA contract and OLP library, e.g. from OpenLoops, Recola or MadLoop. The
+/// subprocesses are taken from the contract, numbered in order of their OLP ids, and all evaluation and sampling
+/// methods are inherited from `Process`.
+#[pyclass(extends = Process)]
+pub(crate) struct BLHAProcess;
+
+impl BLHAProcess {
+    /// Bind the OLP `olp` to a process with the subprocesses of its contract. The particles of the subprocesses must
+    /// be present in `model`.
+    fn bind(
+        olp: Box<dyn OLP>,
+        model: Model,
+        accuracy_threshold: Option<f64>,
+        seed: Option<u64>,
+    ) -> PyResult<Process> {
+        if let Some(pdg) = olp
+            .subprocesses()
+            .iter()
+            .flat_map(|s| s.incoming_pdg.iter().chain(s.outgoing_pdg.iter()))
+            .find(|pdg| !model.contains(**pdg))
+        {
+            return Err(GoSamError::ProcessError(format!(
+                "Particle {pdg} of the contract is missing in the model"
+            )))?;
+        }
+        if accuracy_threshold.is_some() && !olp.reports_accuracy() {
+            return Err(GoSamError::ProcessError(
+                "BLHA1 OLPs report no accuracy to compare with the accuracy threshold".to_owned(),
+            ))?;
+        }
+        let nlo_coupling = olp.contract_option("CorrectionType").map(|c| c.to_owned());
+        let mut process = Process::new(model, nlo_coupling, accuracy_threshold, seed);
+        process.subprocesses = olp.subprocesses().to_vec();
+        process.olp_ids = olp.subprocesses().iter().map(|s| s.ids.clone()).collect();
+        process.olp = Some(olp);
+        return Ok(process);
+    }
+}
+
+#[pymethods]
+impl BLHAProcess {
+    #[new]
+    #[pyo3(signature = (contract_path, library_path, model, accuracy_threshold = None, seed = None, tree_layout = TreeLayout::Born, isolated_copies = false))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python<'_>,
+        contract_path: PathBuf,
+        library_path: PathBuf,
+        model: Model,
+        accuracy_threshold: Option<f64>,
+        seed: Option<u64>,
+        tree_layout: TreeLayout,
+        isolated_copies: bool,
+    ) -> PyResult<(Self, Process)> {
+        let olp = py.allow_threads(|| {
+            OneLoopProvider::new(&contract_path, &library_path, tree_layout, isolated_copies)
+        })?;
+        return Ok((
+            BLHAProcess,
+            Self::bind(Box::new(olp), model, accuracy_threshold, seed)?,
+        ));
+    }
+
+    /// Subprocesses of the contract as `(incoming, outgoing, amplitude_type, olp_ids)`, indexed by the id used by the
+    /// evaluation and sampling methods
+    #[getter]
+    fn subprocesses(slf: PyRef<'_, Self>) -> Vec<ContractSubprocess> {
+        return slf
+            .as_super()
+            .subprocesses
+            .iter()
+            .map(|s| {
+                (
+                    s.incoming_pdg.clone(),
+                    s.outgoing_pdg.clone(),
+                    s.amplitude_type,
+                    s.ids.clone(),
+                )
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{blha::Contract, result::Status};
+    use std::sync::Mutex;
+
+    /// OLP returning its OLP id as every value
+    struct ConstantOLP {
+        contract: Contract,
+        eval_lock: Mutex<()>,
+    }
+
+    impl OLP for ConstantOLP {
+        fn contract(&self) -> &Contract {
+            return &self.contract;
+        }
+
+        fn eval_lock(&self) -> &Mutex<()> {
+            return &self.eval_lock;
+        }
+
+        fn load_copy(&self, _dir: &Path, _index: usize) -> Result<Box<dyn OLP>, BLHAError> {
+            return Err(BLHAError::FunctionError("copies".to_owned()));
+        }
+
+        fn set_parameter(&self, _parameter: &str, _real: f64, _imag: f64) -> Result<(), BLHAError> {
+            return Ok(());
+        }
+
+        fn print_parameters(&self, _filename: &str) -> Result<(), BLHAError> {
+            return Ok(());
+        }
+
+        fn eval(
+            &self,
+            id: i64,
+            _momenta: &[[f64; 4]],
+            _scale: f64,
+        ) -> Result<EvalResult, BLHAError> {
+            return Ok(EvalResult::new(vec![id as f64; self.n_results(id)], 1E-10));
+        }
+    }
+
+    #[test]
+    fn blha_process_test() {
+        let olp = || {
+            let contract = r#"InterfaceVersion BLHA2 | OK
+CorrectionType QCD | OK
+AmplitudeType Tree | OK
+21 21 -> 6 -6 | 1 1
+AmplitudeType Loop | OK
+21 21 -> 6 -6 | 2 2 3
+"#;
+            return Box::new(ConstantOLP {
+                contract: contract.parse().unwrap(),
+                eval_lock: Mutex::new(()),
+            });
         };
-        let mut update = None;
-        if let Some(ref tqdm) = tqdm {
-            update =
-                Some(|inc: usize| Python::with_gil(|py| tqdm.call_method1(py, "update", (inc,))))
+        let mut model = Model::new("/models/sm".into());
+        model.add_particle(21, "g".into(), 0., "ZERO".into(), 3, 8);
+        assert!(BLHAProcess::bind(olp(), model.clone(), None, Some(1)).is_err());
+
+        model.add_particle(6, "t".into(), 173., "MT".into(), 2, 3);
+        model.add_particle(-6, "t~".into(), 173., "MT".into(), 2, -3);
+        let process = BLHAProcess::bind(olp(), model, Some(1E-3), Some(1)).unwrap();
+        assert_eq!(process.subprocesses.len(), 2);
+        assert_eq!(process.subprocesses[1].amplitude_type, AmplitudeType::Loop);
+        assert_eq!(process.olp_ids, [vec![1], vec![2, 3]]);
+        assert!(matches!(
+            process.check_id(2),
+            Err(GoSamError::IndexError(_))
+        ));
+        assert_eq!(process.nlo_coupling.as_deref(), Some("QCD"));
+
+        let olp = process.get_olp("eval").unwrap();
+        let momenta = [[500., 0., 0., 500.], [500., 0., 0., -500.]];
+        let res = process.eval_point(olp, 1, &momenta, 1000.).unwrap();
+        assert_eq!(res.values, [5.; 4]);
+        assert_eq!(res.status, Status::Stable);
+    }
+
+    #[test]
+    fn prepare_point_test() {
+        let contract = "21 21 -> 6 -6 | 1 1";
+        let olp = Box::new(ConstantOLP {
+            contract: contract.parse().unwrap(),
+            eval_lock: Mutex::new(()),
+        });
+        let mut model = Model::new("/models/sm".into());
+        model.add_particle(21, "g".into(), 0., "ZERO".into(), 3, 8);
+        model.add_particle(6, "t".into(), 173., "MT".into(), 2, 3);
+        model.add_particle(-6, "t~".into(), 173., "MT".into(), 2, -3);
+        // The contract has no interface version, i.e. it is a BLHA1 contract without accuracies
+        assert!(!olp.reports_accuracy());
+        let mut process = BLHAProcess::bind(olp, model, None, Some(1)).unwrap();
+        process.point_tolerance = Some(1E-6);
+
+        // Top quarks with the energy of a slightly lighter mass
+        let (e, k) = (500., (500_f64.powi(2) - 172.9_f64.powi(2)).sqrt());
+        let momenta = [
+            [e, 0., 0., e],
+            [e, 0., 0., -e],
+            [e, k, 0., 0.],
+            [e, -k, 0., 0.],
+        ];
+        assert!(matches!(
+            process.prepare_point(0, &momenta),
+            Err(GoSamError::PointError(_))
+        ));
+        process.project_points = true;
+        let projected = process.prepare_point(0, &momenta).unwrap();
+        assert!((scalar(&projected[2], &projected[2]) - 173_f64.powi(2)).abs() < 1E-8);
+
+        let below = momenta.map(|p| p.map(|x| x / 10.));
+        assert!(process.prepare_point(0, &below).is_err());
+    }
+
+    #[test]
+    fn crossed_subprocess_test() {
+        let gosam = GoSamProcess {
+            coupling_orders: IndexMap::from([("QCD".to_owned(), 1)]),
+            contract_options: None,
+            gosam_options: None,
+        };
+        let mut process = Process::new(Model::new("/models/sm".into()), None, None, Some(1));
+        let mut add = |incoming, outgoing, amplitude_type, coupling_powers| {
+            let subprocess =
+                gosam.subprocess(incoming, outgoing, amplitude_type, coupling_powers)?;
+            process.subprocesses.push(subprocess);
+            return Ok::<_, GoSamError>(());
+        };
+        add(vec![2, -1], vec![24, 21], AmplitudeType::Loop, None).unwrap();
+        add(vec![2, -1], vec![24, 21], AmplitudeType::ccTree, None).unwrap();
+        add(vec![-1, 2], vec![24, 21], AmplitudeType::Tree, None).unwrap();
+        add(vec![-1, 2], vec![24, 21], AmplitudeType::Loop, None).unwrap();
+        add(vec![-1, 2], vec![21, 24], AmplitudeType::Loop, None).unwrap();
+        add(vec![6], vec![5, 24], AmplitudeType::Tree, None).unwrap();
+        let powers = IndexMap::from([("QCD".to_owned(), 3)]);
+        add(vec![-1, 2], vec![24, 21], AmplitudeType::Tree, Some(powers)).unwrap();
+        let powers = IndexMap::from([("QED".to_owned(), 1)]);
+        assert!(add(vec![2, -1], vec![24, 21], AmplitudeType::Tree, Some(powers)).is_err());
+        assert_eq!(process.crossed_subprocess(0), Some(3));
+        assert_eq!(process.crossed_subprocess(3), Some(0));
+        assert_eq!(process.crossed_subprocess(1), None);
+        assert_eq!(process.crossed_subprocess(2), None);
+        assert_eq!(process.crossed_subprocess(4), None);
+        assert_eq!(process.crossed_subprocess(5), None);
+        assert_eq!(process.crossed_subprocess(6), None);
+    }
+
+    #[test]
+    fn generated_violation_test() {
+        let s = PyScale::Fixed(1E6);
+        let masses = [0., 0., 173., 173., 125.];
+        let mut rng = substream(1, 0);
+        for _ in 0..100 {
+            let (_, vecs, _, violation) =
+                s.generate(&[21, 21], &masses, Precision::DoubleDouble, &mut rng);
+            assert!(violation[0] < 1E-28 && violation[1] < 1E-28);
+            // The rounded momenta only conserve momentum to double precision
+            let (conservation, on_shell) = momentum_violation(&vecs, &masses, 2);
+            assert!(conservation < 1E-14 && on_shell < 1E-14);
         }
-        let n_update = if n_points >= 1000 { n_points / 1000 } else { 1 };
-        let result = py.allow_threads(|| -> PyResult<_> {
-            let mut result = Vec::with_capacity(n_points);
-            for i in 0..n_points {
-                let (mut renorm_scale, vecs) = rambo((&s).into(), &masses, n_in, &mut rng);
-                if let Some(scale) = scale {
-                    renorm_scale = scale;
+    }
+
+    /// OLP returning the invariants `s_12`, `s_13`, ... of the point as its values
+    struct InvariantOLP {
+        contract: Contract,
+        eval_lock: Mutex<()>,
+    }
+
+    impl OLP for InvariantOLP {
+        fn contract(&self) -> &Contract {
+            return &self.contract;
+        }
+
+        fn eval_lock(&self) -> &Mutex<()> {
+            return &self.eval_lock;
+        }
+
+        fn load_copy(&self, _dir: &Path, _index: usize) -> Result<Box<dyn OLP>, BLHAError> {
+            return Err(BLHAError::FunctionError("copies".to_owned()));
+        }
+
+        fn set_parameter(&self, _parameter: &str, _real: f64, _imag: f64) -> Result<(), BLHAError> {
+            return Ok(());
+        }
+
+        fn print_parameters(&self, _filename: &str) -> Result<(), BLHAError> {
+            return Ok(());
+        }
+
+        fn eval(
+            &self,
+            id: i64,
+            momenta: &[[f64; 4]],
+            _scale: f64,
+        ) -> Result<EvalResult, BLHAError> {
+            let s = invariants(momenta, 2);
+            let values = (0..self.n_results(id)).map(|i| s[i + 1]).collect();
+            return Ok(EvalResult::new(values, 1E-10));
+        }
+    }
+
+    #[test]
+    fn frame_test() {
+        let olp = Box::new(InvariantOLP {
+            contract: "21 21 -> 6 -6 | 1 1".parse().unwrap(),
+            eval_lock: Mutex::new(()),
+        });
+        let mut model = Model::new("/models/sm".into());
+        model.add_particle(21, "g".into(), 0., "ZERO".into(), 3, 8);
+        model.add_particle(6, "t".into(), 173., "MT".into(), 2, 3);
+        model.add_particle(-6, "t~".into(), 173., "MT".into(), 2, -3);
+        let process = BLHAProcess::bind(olp, model, None, Some(1)).unwrap();
+        let olp = process.get_olp("eval").unwrap();
+        let s = PyScale::Fixed(1E6);
+        let masses = process.masses(0);
+        let mut rng = substream(1, 0);
+        let (_, point, _, _) = process.generate(0, &s, &masses, &mut rng);
+        let expected = process.eval_point(olp, 0, &point, 1E3).unwrap();
+
+        let frames = [
+            Frame::CentreOfMass {},
+            Frame::Lab {
+                energy1: 2000.,
+                energy2: 125.,
+            },
+            Frame::RandomAzimuth {},
+            Frame::Rest { index: 2 },
+        ];
+        for frame in frames {
+            assert!(process.check_frame(0, &s, Some(&frame)).is_ok());
+            let mut vecs = point.clone();
+            frame.apply(&mut vecs, 2, &mut rng);
+            let res = process.eval_point(olp, 0, &vecs, 1E3).unwrap();
+            for (a, b) in res.values.iter().zip(&expected.values) {
+                assert!((a - b).abs() < 1E-8 * b.abs(), "{frame:?}");
+            }
+            match frame {
+                Frame::Lab { energy1, energy2 } => {
+                    assert!((vecs[0][0] - energy1).abs() < 1E-9 * energy1);
+                    assert!((vecs[1][0] - energy2).abs() < 1E-9 * energy1);
                 }
-                let vals = lib.eval(id, &vecs, renorm_scale)?;
-                result.push((vecs, vals));
-                if i % n_update == 0 {
-                    if let Some(f) = update {
-                        f(n_update)?;
-                    }
+                Frame::Rest { index } => {
+                    assert!(vecs[index][1..].iter().all(|p| p.abs() < 1E-9));
                 }
+                _ => {}
             }
-            return Ok(result);
-        });
-        if let Some(ref tqdm) = tqdm {
-            tqdm.call_method0(py, "close")?;
         }
-        return result;
+
+        let lab = |energy1, energy2| Some(Frame::Lab { energy1, energy2 });
+        assert!(process.check_frame(0, &s, lab(1000., 0.).as_ref()).is_err());
+        assert!(
+            process
+                .check_frame(0, &s, lab(1000., 500.).as_ref())
+                .is_err()
+        );
+        let uniform = PyScale::Uniform { min: 1E5, max: 1E6 };
+        assert!(
+            process
+                .check_frame(0, &uniform, lab(1000., 250.).as_ref())
+                .is_err()
+        );
+        let rest = Some(Frame::Rest { index: 0 });
+        assert!(process.check_frame(0, &s, rest.as_ref()).is_err());
     }
 }
@@ -0,0 +1,110 @@
+use pyo3::prelude::*;
+
+/// Component of a `Tree`, `Loop` or `LoopInduced` result
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[pyclass]
+pub(crate) enum Component {
+    DoublePole,
+    SinglePole,
+    Finite,
+    Born,
+}
+
+impl Component {
+    /// Select the component from the raw BLHA2 values `[double_pole, single_pole, finite, born]`
+    pub(crate) fn select(&self, values: &[f64]) -> f64 {
+        return values[*self as usize];
+    }
+}
+
+/// Running mean and Monte Carlo error of a series of weights
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Accumulator {
+    n: usize,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Accumulator {
+    pub(crate) fn add(&mut self, x: f64) {
+        self.n += 1;
+        self.sum += x;
+        self.sum_sq += x * x;
+    }
+
+    pub(crate) fn n(&self) -> usize {
+        return self.n;
+    }
+
+    pub(crate) fn mean(&self) -> f64 {
+        return self.sum / self.n as f64;
+    }
+
+    pub(crate) fn error(&self) -> f64 {
+        if self.n < 2 {
+            return f64::INFINITY;
+        }
+        let n = self.n as f64;
+        let mean = self.sum / n;
+        return ((self.sum_sq / n - mean * mean).max(0.) / (n - 1.)).sqrt();
+    }
+}
+
+/// Monte Carlo estimate of an integral
+#[pyclass]
+#[derive(Debug, Clone)]
+pub(crate) struct IntegrationResult {
+    #[pyo3(get)]
+    pub(crate) value: f64,
+    #[pyo3(get)]
+    pub(crate) error: f64,
+    #[pyo3(get)]
+    pub(crate) n_points: usize,
+    #[pyo3(get)]
+    pub(crate) history: Vec<(usize, f64, f64)>,
+}
+
+impl IntegrationResult {
+    pub(crate) fn new(acc: &Accumulator, history: Vec<(usize, f64, f64)>) -> Self {
+        IntegrationResult {
+            value: acc.mean(),
+            error: acc.error(),
+            n_points: acc.n(),
+            history,
+        }
+    }
+}
+
+#[pymethods]
+impl IntegrationResult {
+    #[getter]
+    fn relative_error(&self) -> f64 {
+        return (self.error / self.value).abs();
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "IntegrationResult(value={:e}, error={:e}, n_points={})",
+            self.value, self.error, self.n_points
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fastrand::Rng;
+
+    #[test]
+    fn accumulator_test() {
+        let mut rng = Rng::with_seed(1);
+        let mut acc = Accumulator::default();
+        for _ in 0..1_000_000 {
+            let x = rng.f64();
+            acc.add(3. * x * x);
+        }
+        assert!((acc.mean() - 1.).abs() < 5. * acc.error());
+        // Var(3 x^2) = 9/5 - 1 for x ~ U(0, 1)
+        assert!((acc.error() * 1000. / 0.8_f64.sqrt() - 1.).abs() < 1E-2);
+    }
+}
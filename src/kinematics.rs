@@ -0,0 +1,636 @@
+use num_traits::Float;
+use numpy::{
+    PyArray1, PyArrayDyn, PyArrayMethods, PyReadonlyArrayDyn, PyUntypedArrayMethods, ndarray::Axis,
+};
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+use crate::{
+    collider::boost_z,
+    util::{FloatRandom, scalar},
+};
+
+/// Transverse momentum with respect to the beam axis
+#[inline]
+pub(crate) fn pt(p: &[f64; 4]) -> f64 {
+    p[1].hypot(p[2])
+}
+
+/// Absolute value of the three-momentum
+#[inline]
+pub(crate) fn abs_p(p: &[f64; 4]) -> f64 {
+    pt(p).hypot(p[3])
+}
+
+/// Rapidity `ln((E + p_z) / (E - p_z)) / 2`
+#[inline]
+pub(crate) fn rapidity(p: &[f64; 4]) -> f64 {
+    0.5 * ((p[0] + p[3]) / (p[0] - p[3])).ln()
+}
+
+/// Pseudorapidity `-ln(tan(theta / 2))`
+#[inline]
+pub(crate) fn eta(p: &[f64; 4]) -> f64 {
+    (p[3] / pt(p)).asinh()
+}
+
+/// Azimuthal angle in `(-pi, pi]`
+#[inline]
+pub(crate) fn phi(p: &[f64; 4]) -> f64 {
+    p[2].atan2(p[1])
+}
+
+/// Invariant mass, negative for space-like momenta
+#[inline]
+pub(crate) fn mass(p: &[f64; 4]) -> f64 {
+    let m_sq = scalar(p, p);
+    m_sq.signum() * m_sq.abs().sqrt()
+}
+
+/// Distance `sqrt(dy^2 + dphi^2)` in the rapidity-azimuth plane
+#[inline]
+pub(crate) fn delta_r(p: &[f64; 4], q: &[f64; 4]) -> f64 {
+    let d_phi = (phi(p) - phi(q)).abs();
+    let d_phi = d_phi.min(2. * std::f64::consts::PI - d_phi);
+    (rapidity(p) - rapidity(q)).hypot(d_phi)
+}
+
+/// Boost `p` into a frame moving with velocity `-beta`, i.e. a particle at rest obtains the velocity `beta`
+pub(crate) fn boost(p: &[f64; 4], beta: &[f64; 3]) -> [f64; 4] {
+    let beta_sq = beta[0] * beta[0] + beta[1] * beta[1] + beta[2] * beta[2];
+    if beta_sq == 0. {
+        return *p;
+    }
+    let gamma = 1. / (1. - beta_sq).sqrt();
+    let bp = beta[0] * p[1] + beta[1] * p[2] + beta[2] * p[3];
+    let c = (gamma - 1.) * bp / beta_sq + gamma * p[0];
+    return [
+        gamma * (p[0] + bp),
+        p[1] + c * beta[0],
+        p[2] + c * beta[1],
+        p[3] + c * beta[2],
+    ];
+}
+
+/// Rotate the three-momentum of `p` by `angle` around `axis`
+pub(crate) fn rotate(p: &[f64; 4], axis: &[f64; 3], angle: f64) -> [f64; 4] {
+    let norm = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+    let k = [axis[0] / norm, axis[1] / norm, axis[2] / norm];
+    let v = [p[1], p[2], p[3]];
+    let (sin, cos) = angle.sin_cos();
+    let kv = k[0] * v[0] + k[1] * v[1] + k[2] * v[2];
+    let cross = [
+        k[1] * v[2] - k[2] * v[1],
+        k[2] * v[0] - k[0] * v[2],
+        k[0] * v[1] - k[1] * v[0],
+    ];
+    let mut result = [p[0], 0., 0., 0.];
+    for i in 0..3 {
+        result[i + 1] = v[i] * cos + cross[i] * sin + k[i] * kv * (1. - cos);
+    }
+    return result;
+}
+
+/// Invariants `s_ij = (sigma_i p_i + sigma_j p_j)^2` of all pairs of legs, where `sigma` is `1` for the `n_in`
+/// incoming and `-1` for the outgoing legs, such that `s = s_12`, `t = s_13` and `u = s_14` for `2 -> 2` processes.
+/// The diagonal contains the squared masses `p_i^2`.
+pub(crate) fn invariants(vecs: &[[f64; 4]], n_in: usize) -> Vec<f64> {
+    let n = vecs.len();
+    let sign = |i: usize| if i < n_in { 1. } else { -1. };
+    let mut result = vec![0.; n * n];
+    for i in 0..n {
+        for j in 0..n {
+            result[i * n + j] = if i == j {
+                scalar(&vecs[i], &vecs[i])
+            } else {
+                let p: [f64; 4] =
+                    std::array::from_fn(|mu| sign(i) * vecs[i][mu] + sign(j) * vecs[j][mu]);
+                scalar(&p, &p)
+            };
+        }
+    }
+    return result;
+}
+
+/// Violation of momentum conservation and of the on-shell conditions of the point `vecs` with masses `masses`,
+/// given as the largest deviation of a component of `sum_in p_i - sum_out p_i` relative to the incoming energy and
+/// the largest deviation `|p_i^2 - m_i^2|` relative to `E_i^2`, computed in the floating point type `F`
+pub(crate) fn momentum_violation<F: Float>(vecs: &[[F; 4]], masses: &[F], n_in: usize) -> (F, F) {
+    let sum = |vecs: &[[F; 4]], mu: usize| vecs.iter().fold(F::zero(), |sum, p| sum + p[mu]);
+    let energy = sum(&vecs[..n_in], 0);
+    let mut conservation = F::zero();
+    for mu in 0..=3 {
+        let total = sum(&vecs[..n_in], mu) - sum(&vecs[n_in..], mu);
+        conservation = conservation.max(total.abs() / energy);
+    }
+    let on_shell = vecs
+        .iter()
+        .zip(masses.iter())
+        .map(|(p, m)| {
+            let p2 = p[0] * p[0] - p[1] * p[1] - p[2] * p[2] - p[3] * p[3];
+            (p2 - *m * *m).abs() / (p[0] * p[0])
+        })
+        .fold(F::zero(), F::max);
+    return (conservation, on_shell);
+}
+
+/// Project the point `vecs` onto the mass shells `masses` and exact momentum conservation. The incoming momenta are
+/// put on-shell by adjusting their energies, the outgoing momenta are balanced and rescaled in the center of mass
+/// frame of the incoming momenta. Returns `None` if the incoming momenta do not allow the outgoing masses.
+pub(crate) fn project(vecs: &[[f64; 4]], masses: &[f64], n_in: usize) -> Option<Vec<[f64; 4]>> {
+    let on_shell = |p: &[f64; 4], m: f64| [abs_p(p).hypot(m), p[1], p[2], p[3]];
+    let mut result = vecs
+        .iter()
+        .zip(masses.iter())
+        .map(|(p, m)| on_shell(p, *m))
+        .collect::<Vec<_>>();
+    let total: [f64; 4] = std::array::from_fn(|mu| result[..n_in].iter().map(|p| p[mu]).sum());
+    let s = scalar(&total, &total);
+    if s <= 0. || s.sqrt() <= masses[n_in..].iter().sum() {
+        return None;
+    }
+    let sqrt_s = s.sqrt();
+    let beta = [
+        total[1] / total[0],
+        total[2] / total[0],
+        total[3] / total[0],
+    ];
+    let mut k = result[n_in..]
+        .iter()
+        .map(|p| boost(p, &beta.map(|b| -b)))
+        .collect::<Vec<_>>();
+    let energy: f64 = k.iter().map(|k| k[0]).sum();
+    let residual: [f64; 4] = std::array::from_fn(|mu| k.iter().map(|k| k[mu]).sum());
+    for k in k.iter_mut() {
+        let share = k[0] / energy;
+        for mu in 1..=3 {
+            k[mu] -= share * residual[mu];
+        }
+    }
+    // Newton iteration for the rescaling of the three-momenta such that the energies add up to sqrt(s)
+    let masses_out = &masses[n_in..];
+    let mut x: f64 = 1.;
+    for _ in 0..100 {
+        let mut f = -sqrt_s;
+        let mut df = 0.;
+        for (k, m) in k.iter().zip(masses_out.iter()) {
+            let p_sq = abs_p(k).powi(2);
+            let e = (m * m + x * x * p_sq).sqrt();
+            f += e;
+            df += x * p_sq / e;
+        }
+        if f.abs() <= 4. * f64::EPSILON * sqrt_s || df == 0. {
+            break;
+        }
+        x -= f / df;
+    }
+    for ((p, k), m) in result[n_in..]
+        .iter_mut()
+        .zip(k.iter())
+        .zip(masses_out.iter())
+    {
+        let scaled = on_shell(&[0., x * k[1], x * k[2], x * k[3]], *m);
+        *p = boost(&scaled, &beta);
+    }
+    return Some(result);
+}
+
+/// Momenta of the array `momenta` of shape `(..., 4)`
+fn four_vectors(momenta: &PyReadonlyArrayDyn<'_, f64>) -> PyResult<Vec<[f64; 4]>> {
+    let shape = momenta.shape();
+    if shape.last() != Some(&4) {
+        return Err(PyValueError::new_err(format!(
+            "Expected array of shape (..., 4), got {shape:?}"
+        )));
+    }
+    let momenta = momenta.as_array();
+    return Ok(momenta
+        .lanes(Axis(shape.len() - 1))
+        .into_iter()
+        .map(|p| [p[0], p[1], p[2], p[3]])
+        .collect());
+}
+
+/// Apply `f` to all momenta of the array `momenta` of shape `(..., 4)`, returning an array of shape `(...)`
+fn map_momenta<'py>(
+    momenta: PyReadonlyArrayDyn<'py, f64>,
+    f: impl Fn(&[f64; 4]) -> f64,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    let shape = momenta.shape()[..momenta.ndim().saturating_sub(1)].to_vec();
+    let values = four_vectors(&momenta)?.iter().map(f).collect::<Vec<_>>();
+    return PyArray1::from_vec(momenta.py(), values).reshape(shape);
+}
+
+/// Transverse momentum of all momenta of the array `momenta` of shape `(..., 4)`
+#[pyfunction(name = "pt")]
+pub(crate) fn py_pt(momenta: PyReadonlyArrayDyn<'_, f64>) -> PyResult<Bound<'_, PyArrayDyn<f64>>> {
+    return map_momenta(momenta, pt);
+}
+
+/// Pseudorapidity of all momenta of the array `momenta` of shape `(..., 4)`
+#[pyfunction(name = "eta")]
+pub(crate) fn py_eta(momenta: PyReadonlyArrayDyn<'_, f64>) -> PyResult<Bound<'_, PyArrayDyn<f64>>> {
+    return map_momenta(momenta, eta);
+}
+
+/// Rapidity of all momenta of the array `momenta` of shape `(..., 4)`
+#[pyfunction(name = "rapidity")]
+pub(crate) fn py_rapidity(
+    momenta: PyReadonlyArrayDyn<'_, f64>,
+) -> PyResult<Bound<'_, PyArrayDyn<f64>>> {
+    return map_momenta(momenta, rapidity);
+}
+
+/// Azimuthal angle of all momenta of the array `momenta` of shape `(..., 4)`
+#[pyfunction(name = "phi")]
+pub(crate) fn py_phi(momenta: PyReadonlyArrayDyn<'_, f64>) -> PyResult<Bound<'_, PyArrayDyn<f64>>> {
+    return map_momenta(momenta, phi);
+}
+
+/// Invariant mass of all momenta of the array `momenta` of shape `(..., 4)`
+#[pyfunction(name = "mass")]
+pub(crate) fn py_mass(
+    momenta: PyReadonlyArrayDyn<'_, f64>,
+) -> PyResult<Bound<'_, PyArrayDyn<f64>>> {
+    return map_momenta(momenta, mass);
+}
+
+/// Distance in the rapidity-azimuth plane of the momenta of the arrays `p` and `q` of equal shape `(..., 4)`
+#[pyfunction(name = "delta_r")]
+pub(crate) fn py_delta_r<'py>(
+    p: PyReadonlyArrayDyn<'py, f64>,
+    q: PyReadonlyArrayDyn<'py, f64>,
+) -> PyResult<Bound<'py, PyArrayDyn<f64>>> {
+    if p.shape() != q.shape() {
+        return Err(PyValueError::new_err(format!(
+            "Arrays of different shape {:?} and {:?}",
+            p.shape(),
+            q.shape()
+        )));
+    }
+    let shape = p.shape()[..p.ndim().saturating_sub(1)].to_vec();
+    let values = four_vectors(&p)?
+        .iter()
+        .zip(four_vectors(&q)?.iter())
+        .map(|(p, q)| delta_r(p, q))
+        .collect::<Vec<_>>();
+    return PyArray1::from_vec(p.py(), values).reshape(shape);
+}
+
+/// Four-momentum `(E, p_x, p_y, p_z)`
+#[pyclass(eq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct FourVector {
+    p: [f64; 4],
+}
+
+#[pymethods]
+impl FourVector {
+    #[new]
+    fn new(e: f64, px: f64, py: f64, pz: f64) -> Self {
+        return FourVector { p: [e, px, py, pz] };
+    }
+
+    #[getter]
+    fn e(&self) -> f64 {
+        return self.p[0];
+    }
+
+    #[getter]
+    fn px(&self) -> f64 {
+        return self.p[1];
+    }
+
+    #[getter]
+    fn py(&self) -> f64 {
+        return self.p[2];
+    }
+
+    #[getter]
+    fn pz(&self) -> f64 {
+        return self.p[3];
+    }
+
+    #[getter]
+    fn pt(&self) -> f64 {
+        return pt(&self.p);
+    }
+
+    #[getter]
+    fn abs_p(&self) -> f64 {
+        return abs_p(&self.p);
+    }
+
+    #[getter]
+    fn eta(&self) -> f64 {
+        return eta(&self.p);
+    }
+
+    #[getter]
+    fn rapidity(&self) -> f64 {
+        return rapidity(&self.p);
+    }
+
+    #[getter]
+    fn phi(&self) -> f64 {
+        return phi(&self.p);
+    }
+
+    #[getter]
+    fn m(&self) -> f64 {
+        return mass(&self.p);
+    }
+
+    #[getter]
+    fn m2(&self) -> f64 {
+        return scalar(&self.p, &self.p);
+    }
+
+    /// Velocity `p / E` of the rest frame
+    #[getter]
+    fn beta(&self) -> [f64; 3] {
+        return [
+            self.p[1] / self.p[0],
+            self.p[2] / self.p[0],
+            self.p[3] / self.p[0],
+        ];
+    }
+
+    fn dot(&self, other: &FourVector) -> f64 {
+        return scalar(&self.p, &other.p);
+    }
+
+    fn delta_r(&self, other: &FourVector) -> f64 {
+        return delta_r(&self.p, &other.p);
+    }
+
+    /// Boost into a frame moving with velocity `-beta`
+    fn boost(&self, beta: [f64; 3]) -> FourVector {
+        return FourVector {
+            p: boost(&self.p, &beta),
+        };
+    }
+
+    /// Boost into the rest frame of `other`
+    fn boost_to_rest_frame(&self, other: &FourVector) -> FourVector {
+        let beta = other.beta();
+        return self.boost([-beta[0], -beta[1], -beta[2]]);
+    }
+
+    /// Rotate by `angle` around `axis`
+    fn rotate(&self, axis: [f64; 3], angle: f64) -> FourVector {
+        return FourVector {
+            p: rotate(&self.p, &axis, angle),
+        };
+    }
+
+    fn tolist(&self) -> [f64; 4] {
+        return self.p;
+    }
+
+    fn __add__(&self, other: &FourVector) -> FourVector {
+        return FourVector {
+            p: std::array::from_fn(|mu| self.p[mu] + other.p[mu]),
+        };
+    }
+
+    fn __sub__(&self, other: &FourVector) -> FourVector {
+        return FourVector {
+            p: std::array::from_fn(|mu| self.p[mu] - other.p[mu]),
+        };
+    }
+
+    fn __neg__(&self) -> FourVector {
+        return FourVector {
+            p: self.p.map(|p| -p),
+        };
+    }
+
+    fn __mul__(&self, factor: f64) -> FourVector {
+        return FourVector {
+            p: self.p.map(|p| factor * p),
+        };
+    }
+
+    fn __rmul__(&self, factor: f64) -> FourVector {
+        return self.__mul__(factor);
+    }
+
+    fn __getitem__(&self, index: usize) -> PyResult<f64> {
+        return self.p.get(index).copied().ok_or_else(|| {
+            pyo3::exceptions::PyIndexError::new_err(format!("Index {index} out of range"))
+        });
+    }
+
+    fn __len__(&self) -> usize {
+        return 4;
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "FourVector({:e}, {:e}, {:e}, {:e})",
+            self.p[0], self.p[1], self.p[2], self.p[3]
+        );
+    }
+}
+
+/// Frame of the momenta of generated phase-space points. RAMBO points are generated in the center of mass frame of the
+/// incoming particles with the beams along the z-axis, points of a hadronic scale in the lab frame of the collider.
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Frame {
+    /// Rest frame of the sum of the incoming momenta
+    #[pyo3(constructor = ())]
+    CentreOfMass {},
+    /// Lab frame of two beams with energies `energy1` and `energy2` colliding head-on, i.e. the center of mass frame
+    /// boosted along the z-axis by the rapidity `ln(energy1 / energy2) / 2`. Requires two incoming particles and a
+    /// fixed scale `s = 4 energy1 energy2`.
+    #[pyo3(constructor = (energy1, energy2))]
+    Lab { energy1: f64, energy2: f64 },
+    /// Rotation of the generated point around the beam axis by a uniformly distributed angle
+    #[pyo3(constructor = ())]
+    RandomAzimuth {},
+    /// Rest frame of the massive leg `index`
+    #[pyo3(constructor = (index))]
+    Rest { index: usize },
+}
+
+impl Frame {
+    /// Transform the point `vecs` with `n_in` incoming particles into the frame. Only `RandomAzimuth` consumes a random
+    /// number.
+    pub(crate) fn apply(
+        &self,
+        vecs: &mut [[f64; 4]],
+        n_in: usize,
+        rng: &mut impl FloatRandom<f64>,
+    ) {
+        match self {
+            Frame::CentreOfMass {} | Frame::Lab { .. } => {
+                let total: [f64; 4] =
+                    std::array::from_fn(|mu| vecs[..n_in].iter().map(|p| p[mu]).sum());
+                let beta = [
+                    -total[1] / total[0],
+                    -total[2] / total[0],
+                    -total[3] / total[0],
+                ];
+                vecs.iter_mut().for_each(|p| *p = boost(p, &beta));
+                if let Frame::Lab { energy1, energy2 } = self {
+                    boost_z(vecs, 0.5 * (energy1 / energy2).ln());
+                }
+            }
+            Frame::RandomAzimuth {} => {
+                let angle = 2. * std::f64::consts::PI * rng.generate();
+                vecs.iter_mut()
+                    .for_each(|p| *p = rotate(p, &[0., 0., 1.], angle));
+            }
+            Frame::Rest { index } => {
+                let p = vecs[*index];
+                let beta = [-p[1] / p[0], -p[2] / p[0], -p[3] / p[0]];
+                vecs.iter_mut().for_each(|p| *p = boost(p, &beta));
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl Frame {
+    fn __repr__(&self) -> String {
+        return match self {
+            Frame::CentreOfMass {} => "Frame.CentreOfMass()".to_owned(),
+            Frame::Lab { energy1, energy2 } => format!("Frame.Lab({energy1:e}, {energy2:e})"),
+            Frame::RandomAzimuth {} => "Frame.RandomAzimuth()".to_owned(),
+            Frame::Rest { index } => format!("Frame.Rest({index})"),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn kinematics_test() {
+        let p = [50., 10., 20., 30.];
+        let m = mass(&p);
+        assert!((m * m - 50_f64.powi(2) + 1400.).abs() < 1E-10);
+        assert!((pt(&p) - 500_f64.sqrt()).abs() < 1E-12);
+        assert!((abs_p(&p) - 1400_f64.sqrt()).abs() < 1E-12);
+        assert!((eta(&p) - 0.5 * ((abs_p(&p) + p[3]) / (abs_p(&p) - p[3])).ln()).abs() < 1E-12);
+        let massless = [5., 3., 0., 4.];
+        assert!((eta(&massless) - rapidity(&massless)).abs() < 1E-12);
+        // Delta phi wraps around at pi
+        let q = [10., -10. * (0.9 * PI).cos(), 10. * (0.9 * PI).sin(), 0.];
+        let r = [10., -10. * (0.9 * PI).cos(), -10. * (0.9 * PI).sin(), 0.];
+        assert!((delta_r(&q, &r) - 0.2 * PI).abs() < 1E-12);
+
+        // Boosts and rotations preserve the invariant mass, the boost to the rest frame removes the momentum
+        let beta = [p[1] / p[0], p[2] / p[0], p[3] / p[0]];
+        let rest = boost(&p, &beta.map(|b| -b));
+        assert!((rest[0] - m).abs() < 1E-10);
+        assert!(rest[1..].iter().all(|p| p.abs() < 1E-10));
+        let back = boost(&rest, &beta);
+        assert!(
+            back.iter()
+                .zip(p.iter())
+                .all(|(a, b)| (a - b).abs() < 1E-10)
+        );
+        let rotated = rotate(&p, &[0., 0., 2.], 0.5);
+        assert!((pt(&rotated) - pt(&p)).abs() < 1E-12);
+        assert!((phi(&rotated) - phi(&p) - 0.5).abs() < 1E-12);
+        assert!((mass(&rotate(&p, &[1., 2., 3.], 1.3)) - m).abs() < 1E-10);
+    }
+
+    #[test]
+    fn invariants_test() {
+        let m: f64 = 173.;
+        let e: f64 = 500.;
+        let k = (e * e - m * m).sqrt();
+        let (sin, cos) = 0.3_f64.sin_cos();
+        let vecs = [
+            [e, 0., 0., e],
+            [e, 0., 0., -e],
+            [e, k * sin, 0., k * cos],
+            [e, -k * sin, 0., -k * cos],
+        ];
+        let s = invariants(&vecs, 2);
+        assert!((s[1] - 4. * e * e).abs() < 1E-8);
+        assert!((s[1] + s[2] + s[3] - 2. * m * m).abs() < 1E-8);
+        assert!((s[2 * 4 + 2] - m * m).abs() < 1E-8);
+        assert_eq!(s[2], s[2 * 4]);
+
+        let masses = [0., 0., m, m];
+        let (conservation, on_shell) = momentum_violation(&vecs, &masses, 2);
+        assert!(conservation < 1E-15 && on_shell < 1E-15);
+        let mut shifted = vecs;
+        shifted[3][1] += 1E-6 * e;
+        let (conservation, on_shell) = momentum_violation(&shifted, &masses, 2);
+        assert!((conservation - 5E-7).abs() < 1E-12);
+        assert!(on_shell > 1E-9);
+
+        let mut boosted = shifted;
+        crate::collider::boost_z(&mut boosted, 0.4);
+        let projected = project(&boosted, &masses, 2).unwrap();
+        let (conservation, on_shell) = momentum_violation(&projected, &masses, 2);
+        assert!(conservation < 1E-15 && on_shell < 1E-14);
+        for (p, q) in projected.iter().zip(boosted.iter()) {
+            for mu in 0..=3 {
+                assert!((p[mu] - q[mu]).abs() < 1E-5 * e);
+            }
+        }
+        assert_eq!(project(&vecs, &[0., 0., 2. * e, m], 2), None);
+    }
+
+    #[test]
+    fn frame_test() {
+        use crate::rambo::{Scale, rambo};
+        use fastrand::Rng;
+
+        // t t~ H production at sqrt(s) = 1 TeV, boosted along the z-axis as a hadronic point would be
+        let (e1, e2) = (800., 312.5);
+        let masses = [0., 0., 173., 173., 125.];
+        let mut rng = Rng::with_seed(1);
+        let (_, mut vecs, _) = rambo(Scale::Fixed(4. * e1 * e2), &masses, 2, &mut rng);
+        crate::collider::boost_z(&mut vecs, 0.3);
+        let before = invariants(&vecs, 2);
+        let frames = [
+            Frame::CentreOfMass {},
+            Frame::Lab {
+                energy1: e1,
+                energy2: e2,
+            },
+            Frame::RandomAzimuth {},
+            Frame::Rest { index: 4 },
+        ];
+        for frame in frames.iter() {
+            let mut boosted = vecs.clone();
+            frame.apply(&mut boosted, 2, &mut rng);
+            for (a, b) in invariants(&boosted, 2).iter().zip(before.iter()) {
+                assert!((a - b).abs() < 1E-8 * b.abs().max(1.));
+            }
+            for mu in 0..=3 {
+                let balance = boosted[0][mu] + boosted[1][mu]
+                    - boosted[2..].iter().map(|p| p[mu]).sum::<f64>();
+                assert!(balance.abs() < 1E-9);
+            }
+            match frame {
+                Frame::CentreOfMass {} => {
+                    assert!((boosted[0][3] + boosted[1][3]).abs() < 1E-9);
+                    assert!((boosted[0][0] - boosted[1][0]).abs() < 1E-9);
+                }
+                Frame::Lab { .. } => {
+                    assert!((boosted[0][0] - e1).abs() < 1E-9);
+                    assert!((boosted[1][0] - e2).abs() < 1E-9);
+                }
+                Frame::RandomAzimuth {} => {
+                    assert!((boosted[2][3] - vecs[2][3]).abs() < 1E-9);
+                    assert!((pt(&boosted[2]) - pt(&vecs[2])).abs() < 1E-9);
+                    assert!((phi(&boosted[2]) - phi(&vecs[2])).abs() > 1E-6);
+                }
+                Frame::Rest { index } => {
+                    assert!(abs_p(&boosted[*index]) < 1E-9);
+                    assert!((boosted[*index][0] - 125.).abs() < 1E-9);
+                }
+            }
+        }
+    }
+}
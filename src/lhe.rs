@@ -0,0 +1,283 @@
+use pyo3::prelude::*;
+use std::io::prelude::*;
+
+/// Event weights passed from Python, either a single weight or a list of named weights per event
+#[derive(FromPyObject)]
+pub(crate) enum EventWeights {
+    Multiple(Vec<Vec<f64>>),
+    Single(Vec<f64>),
+}
+
+impl EventWeights {
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            EventWeights::Multiple(w) => w.len(),
+            EventWeights::Single(w) => w.len(),
+        }
+    }
+
+    pub(crate) fn get(&self, i: usize) -> &[f64] {
+        match self {
+            EventWeights::Multiple(w) => &w[i],
+            EventWeights::Single(w) => std::slice::from_ref(&w[i]),
+        }
+    }
+}
+
+/// Event scales passed from Python, either a common scale or one scale per event
+#[derive(FromPyObject)]
+pub(crate) enum EventScales {
+    Fixed(f64),
+    PerEvent(Vec<f64>),
+}
+
+/// First colour tag, following the convention of common event generators
+const FIRST_COLOUR_TAG: usize = 501;
+
+/// Leading-colour flow of a subprocess with colour representations `colours`, e.g. `3` for quarks, `-3` for
+/// antiquarks and `8` for gluons, of which the first `n_in` are incoming. Returns the `(colour, anticolour)` tags of
+/// each leg. Colour lines preferably run through the process, connecting incoming to outgoing quarks and incoming to
+/// outgoing antiquarks. The gluons are attached to the first of these strings, and purely gluonic states form a
+/// closed loop. Returns `None` for other colour representations or if the legs cannot form a colour singlet.
+pub(crate) fn colour_flow(colours: &[i64], n_in: usize) -> Option<Vec<(usize, usize)>> {
+    // Incoming quarks are outgoing antiquarks and vice versa
+    let outgoing = |i: usize| {
+        if i < n_in && colours[i].abs() == 3 {
+            -colours[i]
+        } else {
+            colours[i]
+        }
+    };
+    let legs = |colour: i64| (0..colours.len()).filter(move |&i| outgoing(i) == colour);
+    if (0..colours.len()).any(|i| !matches!(outgoing(i), 1 | 3 | -3 | 8)) {
+        return None;
+    }
+    // Pairing outgoing quarks with incoming quarks first lets the colour flow through the process
+    let quarks = legs(3)
+        .filter(|&i| i >= n_in)
+        .chain(legs(3).filter(|&i| i < n_in))
+        .collect::<Vec<_>>();
+    let antiquarks = legs(-3).collect::<Vec<_>>();
+    let gluons = legs(8).collect::<Vec<_>>();
+    if quarks.len() != antiquarks.len() || (quarks.is_empty() && gluons.len() == 1) {
+        return None;
+    }
+    let mut flow = vec![(0, 0); colours.len()];
+    let mut tag = FIRST_COLOUR_TAG;
+    if quarks.is_empty() {
+        // Closed gluon loop, the anticolour of the first gluon is the colour of the last one
+        for (k, &g) in gluons.iter().enumerate() {
+            flow[g] = (
+                tag + k,
+                if k == 0 {
+                    tag + gluons.len() - 1
+                } else {
+                    tag + k - 1
+                },
+            );
+        }
+    } else {
+        for (k, (&q, &qbar)) in quarks.iter().zip(antiquarks.iter()).enumerate() {
+            flow[q].0 = tag;
+            if k == 0 {
+                for &g in gluons.iter() {
+                    flow[g] = (tag + 1, tag);
+                    tag += 1;
+                }
+            }
+            flow[qbar].1 = tag;
+            tag += 1;
+        }
+    }
+    // Colour and anticolour of incoming legs are exchanged when crossing them back
+    for (colour, anticolour) in flow[..n_in].iter_mut() {
+        std::mem::swap(colour, anticolour);
+    }
+    return Some(flow);
+}
+
+/// Process information of the `<init>` block of a Les Houches Event file (hep-ph/0609017, 1405.1067)
+pub(crate) struct LHEInit<'a> {
+    pub(crate) incoming_pdg: &'a [i64],
+    pub(crate) outgoing_pdg: &'a [i64],
+    pub(crate) masses: &'a [f64],
+    /// `(colour, anticolour)` tags of each leg, see `colour_flow`
+    pub(crate) colours: &'a [(usize, usize)],
+    pub(crate) beam_pdg: [i64; 2],
+    pub(crate) beam_energies: [f64; 2],
+    pub(crate) cross_section: f64,
+    pub(crate) cross_section_error: f64,
+    pub(crate) max_weight: f64,
+    pub(crate) unweighted: bool,
+    pub(crate) weight_names: &'a [String],
+}
+
+/// Writer for Les Houches Event files in version 3.0 with a single process
+pub(crate) struct LHEWriter<'a, W: Write> {
+    writer: W,
+    init: LHEInit<'a>,
+}
+
+impl<'a, W: Write> LHEWriter<'a, W> {
+    pub(crate) fn new(mut writer: W, init: LHEInit<'a>) -> Result<Self, std::io::Error> {
+        writeln!(writer, r#"<LesHouchesEvents version="3.0">"#)?;
+        writeln!(writer, "<header>")?;
+        writeln!(writer, "<!-- File generated by pygs -->")?;
+        if !init.weight_names.is_empty() {
+            writeln!(writer, "<initrwgt>")?;
+            writeln!(writer, r#"<weightgroup name="pygs">"#)?;
+            for name in init.weight_names.iter() {
+                writeln!(writer, r#"<weight id="{name}">{name}</weight>"#)?;
+            }
+            writeln!(writer, "</weightgroup>")?;
+            writeln!(writer, "</initrwgt>")?;
+        }
+        writeln!(writer, "</header>")?;
+        writeln!(writer, "<init>")?;
+        writeln!(
+            writer,
+            "{} {} {:.10e} {:.10e} 0 0 0 0 {} 1",
+            init.beam_pdg[0],
+            init.beam_pdg[1],
+            init.beam_energies[0],
+            init.beam_energies[1],
+            if init.unweighted { 3 } else { -4 }
+        )?;
+        writeln!(
+            writer,
+            "{:.10e} {:.10e} {:.10e} 1",
+            init.cross_section, init.cross_section_error, init.max_weight
+        )?;
+        writeln!(writer, "</init>")?;
+        return Ok(LHEWriter { writer, init });
+    }
+
+    /// Write a single event with momenta `momenta`, scale `scale` and weights `weights`, where the first weight is
+    /// the nominal event weight
+    pub(crate) fn write_event(
+        &mut self,
+        momenta: &[[f64; 4]],
+        weights: &[f64],
+        scale: f64,
+    ) -> Result<(), std::io::Error> {
+        let init = &self.init;
+        let n_in = init.incoming_pdg.len();
+        writeln!(self.writer, "<event>")?;
+        writeln!(
+            self.writer,
+            "{} 1 {:.10e} {:.10e} -1 -1",
+            momenta.len(),
+            weights[0],
+            scale
+        )?;
+        for (i, (pdg, p)) in init
+            .incoming_pdg
+            .iter()
+            .chain(init.outgoing_pdg.iter())
+            .zip(momenta.iter())
+            .enumerate()
+        {
+            let (status, mothers) = if i < n_in {
+                (-1, (0, 0))
+            } else {
+                (1, (1, n_in))
+            };
+            let (colour, anticolour) = init.colours[i];
+            writeln!(
+                self.writer,
+                "{pdg} {status} {} {} {colour} {anticolour} {:.10e} {:.10e} {:.10e} {:.10e} {:.10e} 0 9",
+                mothers.0, mothers.1, p[1], p[2], p[3], p[0], init.masses[i]
+            )?;
+        }
+        if !init.weight_names.is_empty() {
+            writeln!(self.writer, "<rwgt>")?;
+            for (name, w) in init.weight_names.iter().zip(weights.iter()) {
+                writeln!(self.writer, r#"<wgt id="{name}">{w:.10e}</wgt>"#)?;
+            }
+            writeln!(self.writer, "</rwgt>")?;
+        }
+        writeln!(self.writer, "</event>")?;
+        Ok(())
+    }
+
+    pub(crate) fn finish(mut self) -> Result<(), std::io::Error> {
+        writeln!(self.writer, "</LesHouchesEvents>")?;
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lhe_test() {
+        let names = vec!["finite".to_owned(), "born".to_owned()];
+        let init = LHEInit {
+            incoming_pdg: &[21, 21],
+            outgoing_pdg: &[25, 25],
+            masses: &[0., 0., 125., 125.],
+            colours: &[(501, 502), (502, 501), (0, 0), (0, 0)],
+            beam_pdg: [21, 21],
+            beam_energies: [250., 250.],
+            cross_section: 1.5,
+            cross_section_error: 0.1,
+            max_weight: 3.,
+            unweighted: false,
+            weight_names: &names,
+        };
+        let mut buffer = Vec::new();
+        let mut writer = LHEWriter::new(&mut buffer, init).unwrap();
+        let momenta = [
+            [250., 0., 0., 250.],
+            [250., 0., 0., -250.],
+            [250., 150., 0., 0.],
+            [250., -150., 0., 0.],
+        ];
+        writer.write_event(&momenta, &[2., 1.], 1000.).unwrap();
+        writer.write_event(&momenta, &[-1., 0.5], 1000.).unwrap();
+        writer.finish().unwrap();
+        let content = String::from_utf8(buffer).unwrap();
+        let lines = content.lines().collect::<Vec<_>>();
+        assert_eq!(lines[0], r#"<LesHouchesEvents version="3.0">"#);
+        assert_eq!(*lines.last().unwrap(), "</LesHouchesEvents>");
+        assert_eq!(content.matches("<event>").count(), 2);
+        assert_eq!(content.matches(r#"<wgt id="born">"#).count(), 2);
+        let init_line = lines[lines.iter().position(|l| *l == "<init>").unwrap() + 1];
+        assert!(init_line.starts_with("21 21 2.5"));
+        assert!(init_line.ends_with(" -4 1"));
+        let event = lines.iter().position(|l| *l == "<event>").unwrap();
+        assert!(lines[event + 1].starts_with("4 1 2.0"));
+        let higgs = lines[event + 4].split_whitespace().collect::<Vec<_>>();
+        assert_eq!(higgs.len(), 13);
+        assert_eq!(&higgs[..6], &["25", "1", "1", "2", "0", "0"]);
+        let gluon = lines[event + 2].split_whitespace().collect::<Vec<_>>();
+        assert_eq!(&gluon[..6], &["21", "-1", "0", "0", "501", "502"]);
+        assert_eq!(higgs[9].parse::<f64>().unwrap(), 250.);
+        assert_eq!(higgs[10].parse::<f64>().unwrap(), 125.);
+    }
+
+    #[test]
+    fn colour_flow_test() {
+        // u u~ -> t t~ g: the gluon is radiated on the line from the up quark to the top quark, the antiquark line
+        // connects the incoming up antiquark to the top antiquark
+        assert_eq!(
+            colour_flow(&[3, -3, 3, -3, 8], 2),
+            Some(vec![(502, 0), (0, 503), (501, 0), (0, 503), (502, 501)])
+        );
+        // g g -> g g: closed loop
+        assert_eq!(
+            colour_flow(&[8, 8, 8, 8], 2),
+            Some(vec![(504, 501), (501, 502), (503, 502), (504, 503)])
+        );
+        // u d -> u d: two lines through the process
+        assert_eq!(
+            colour_flow(&[3, 3, 3, 3], 2),
+            Some(vec![(501, 0), (502, 0), (501, 0), (502, 0)])
+        );
+        assert_eq!(colour_flow(&[8, 1, 3], 2), None);
+        assert_eq!(colour_flow(&[1, 1, 8], 2), None);
+        assert_eq!(colour_flow(&[6, -6, 1], 2), None);
+    }
+}
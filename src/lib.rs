@@ -1,22 +1,67 @@
 mod blha;
+mod collider;
+mod cuts;
+mod double_double;
 mod gosam;
+mod integrate;
+mod kinematics;
+mod lhe;
 mod model;
+mod pdf;
 mod rambo;
+mod rescue;
+mod result;
+mod unweight;
 mod util;
+mod validate;
+mod vegas;
 
 use crate::{
-    gosam::{GoSamProcess, PyScale},
+    collider::{Beam, Collider},
+    cuts::Cut,
+    gosam::{BLHAProcess, GoSamProcess, Precision, Process, PyScale},
+    integrate::{Component, IntegrationResult},
+    kinematics::{FourVector, Frame, py_delta_r, py_eta, py_mass, py_phi, py_pt, py_rapidity},
     model::Model,
+    rescue::Rescue,
+    result::{ColorCorrelatedResult, LoopResult, SpinCorrelatedResult, Status},
+    unweight::UnweightedEvents,
+    validate::{PoleCheck, ValidationResult},
 };
-use blha::AmplitudeType;
+use blha::{AmplitudeType, TreeLayout};
 use pyo3::prelude::*;
 
 #[pymodule]
 fn pygs(m: &Bound<'_, PyModule>) -> PyResult<()> {
     pyo3_log::init();
     m.add_class::<Model>()?;
+    m.add_class::<Process>()?;
     m.add_class::<GoSamProcess>()?;
+    m.add_class::<BLHAProcess>()?;
     m.add_class::<AmplitudeType>()?;
+    m.add_class::<TreeLayout>()?;
     m.add_class::<PyScale>()?;
+    m.add_class::<LoopResult>()?;
+    m.add_class::<ColorCorrelatedResult>()?;
+    m.add_class::<SpinCorrelatedResult>()?;
+    m.add_class::<Component>()?;
+    m.add_class::<IntegrationResult>()?;
+    m.add_class::<UnweightedEvents>()?;
+    m.add_class::<Beam>()?;
+    m.add_class::<Collider>()?;
+    m.add_class::<Cut>()?;
+    m.add_class::<FourVector>()?;
+    m.add_class::<Frame>()?;
+    m.add_class::<ValidationResult>()?;
+    m.add_class::<PoleCheck>()?;
+    m.add_class::<Rescue>()?;
+    m.add_class::<Status>()?;
+    m.add_class::<Precision>()?;
+    m.add_function(wrap_pyfunction!(py_pt, m)?)?;
+    m.add_function(wrap_pyfunction!(py_eta, m)?)?;
+    m.add_function(wrap_pyfunction!(py_rapidity, m)?)?;
+    m.add_function(wrap_pyfunction!(py_phi, m)?)?;
+    m.add_function(wrap_pyfunction!(py_mass, m)?)?;
+    m.add_function(wrap_pyfunction!(py_delta_r, m)?)?;
     Ok(())
 }
@@ -10,10 +10,38 @@ pub(crate) struct Model {
 }
 
 impl Model {
+    /// Whether the model contains the particle with PDG id `id`
+    pub(crate) fn contains(&self, id: i64) -> bool {
+        return self.particles.contains_key(&id);
+    }
+
     pub(crate) fn get_mass(&self, id: i64) -> f64 {
         return self.particles.get(&id).unwrap().mass;
     }
 
+    /// Number of spin and colour degrees of freedom of particle `id`
+    pub(crate) fn degrees_of_freedom(&self, id: i64) -> usize {
+        let p = self.particles.get(&id).unwrap();
+        let spin = if p.mass == 0. && p.spin > 1 {
+            2
+        } else {
+            p.spin.unsigned_abs()
+        };
+        return (spin * p.color.unsigned_abs()) as usize;
+    }
+
+    /// Colour representation of particle `id`, e.g. `3` for quarks and `8` for gluons
+    pub(crate) fn color(&self, id: i64) -> i64 {
+        return self.particles.get(&id).unwrap().color;
+    }
+
+    /// Number of massless quark flavours
+    pub(crate) fn n_light_flavours(&self) -> usize {
+        return (1..=6)
+            .filter(|id| self.particles.get(id).is_some_and(|p| p.mass == 0.))
+            .count();
+    }
+
     pub(crate) fn update_mass(&mut self, ident: &str, value: f64) {
         for p in self.particles.values_mut() {
             if p.mass_ident == ident {
@@ -26,14 +54,23 @@ impl Model {
 #[pymethods]
 impl Model {
     #[new]
-    fn new(path: PathBuf) -> Model {
+    pub(crate) fn new(path: PathBuf) -> Model {
         Model {
             path,
             particles: HashMap::new(),
         }
     }
 
-    fn add_particle(&mut self, pdg_id: i64, name: String, mass: f64, mass_ident: String) {
+    #[pyo3(signature = (pdg_id, name, mass, mass_ident, spin = 1, color = 1))]
+    pub(crate) fn add_particle(
+        &mut self,
+        pdg_id: i64,
+        name: String,
+        mass: f64,
+        mass_ident: String,
+        spin: i64,
+        color: i64,
+    ) {
         self.particles.insert(
             pdg_id,
             Particle {
@@ -41,6 +78,8 @@ impl Model {
                 name,
                 mass,
                 mass_ident,
+                spin,
+                color,
             },
         );
     }
@@ -60,6 +99,8 @@ struct Particle {
     name: String,
     mass: f64,
     mass_ident: String,
+    spin: i64,
+    color: i64,
 }
 
 #[cfg(test)]
@@ -68,12 +109,19 @@ mod tests {
     #[test]
     fn model_test() {
         let mut m = Model::new(PathBuf::from("/tmp"));
-        m.add_particle(6, "t".into(), 172., "MT".into());
-        m.add_particle(-6, "t~".into(), 172., "MT".into());
-        m.add_particle(1, "d".into(), 0., "MD".into());
+        m.add_particle(6, "t".into(), 172., "MT".into(), 2, 3);
+        m.add_particle(-6, "t~".into(), 172., "MT".into(), 2, -3);
+        m.add_particle(1, "d".into(), 0., "MD".into(), 2, 3);
+        m.add_particle(21, "g".into(), 0., "ZERO".into(), 3, 8);
+        m.add_particle(23, "Z".into(), 91.1876, "MZ".into(), 3, 1);
 
         m.update_mass("MT", 173.);
 
         assert_eq!(m.particles[&-6].mass, 173.);
+        assert_eq!(m.degrees_of_freedom(-6), 6);
+        assert_eq!(m.degrees_of_freedom(21), 16);
+        assert_eq!(m.degrees_of_freedom(23), 3);
+        assert_eq!(m.color(-6), -3);
+        assert_eq!(m.n_light_flavours(), 1);
     }
-}
\ No newline at end of file
+}
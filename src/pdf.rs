@@ -0,0 +1,186 @@
+use std::path::Path;
+
+/// Subgrid of an LHAPDF `lhagrid1` member, valid between the first and last `Q` knot
+#[derive(Debug, Clone, PartialEq)]
+struct Subgrid {
+    ln_x: Vec<f64>,
+    ln_q2: Vec<f64>,
+    pids: Vec<i64>,
+    /// `x f(x, Q)` at `xf[(ix * n_q + iq) * n_pids + ipid]`
+    xf: Vec<f64>,
+}
+
+impl Subgrid {
+    fn xfx(&self, ipid: usize, ln_x: f64, ln_q2: f64) -> f64 {
+        let (ix, tx) = locate(&self.ln_x, ln_x);
+        let (iq, tq) = locate(&self.ln_q2, ln_q2);
+        let n_q = self.ln_q2.len();
+        let n_pids = self.pids.len();
+        let at = |ix: usize, iq: usize| self.xf[(ix * n_q + iq) * n_pids + ipid];
+        if n_q == 1 {
+            return (1. - tx) * at(ix, 0) + tx * at(ix + 1, 0);
+        }
+        return (1. - tx) * ((1. - tq) * at(ix, iq) + tq * at(ix, iq + 1))
+            + tx * ((1. - tq) * at(ix + 1, iq) + tq * at(ix + 1, iq + 1));
+    }
+}
+
+/// Index of the interval of `knots` containing `v` and the relative position within it, clamped to the grid
+fn locate(knots: &[f64], v: f64) -> (usize, f64) {
+    if knots.len() == 1 {
+        return (0, 0.);
+    }
+    let v = v.clamp(knots[0], knots[knots.len() - 1]);
+    let i = knots
+        .partition_point(|k| *k <= v)
+        .saturating_sub(1)
+        .min(knots.len() - 2);
+    return (i, (v - knots[i]) / (knots[i + 1] - knots[i]));
+}
+
+/// Parton density of a single member of an LHAPDF set in the `lhagrid1` format (1412.7420).
+///
+/// The grid is interpolated bilinearly in `ln x` and `ln Q^2`. Outside of the grid, the densities are frozen at the
+/// boundary.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Pdf {
+    subgrids: Vec<Subgrid>,
+}
+
+impl Pdf {
+    /// Load the member `member` of the set `path`, which is either the path of a member file or of the set directory
+    pub(crate) fn load(path: &Path, member: usize) -> Result<Self, std::io::Error> {
+        let path = if path.is_dir() {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            path.join(format!("{name}_{member:04}.dat"))
+        } else {
+            path.to_path_buf()
+        };
+        let content = std::fs::read_to_string(&path)?;
+        return Pdf::parse(&content).map_err(|msg| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {msg}", path.display()),
+            )
+        });
+    }
+
+    pub(crate) fn parse(content: &str) -> Result<Self, String> {
+        let mut blocks = content.split("\n---");
+        let header = blocks.next().unwrap_or_default();
+        if let Some(format) = header
+            .lines()
+            .find_map(|l| l.trim().strip_prefix("Format:"))
+            && format.trim() != "lhagrid1"
+        {
+            return Err(format!("unsupported format '{}'", format.trim()));
+        }
+        let mut subgrids = Vec::new();
+        for block in blocks {
+            let mut lines = block.lines().map(|l| l.trim()).filter(|l| !l.is_empty());
+            let Some(x_line) = lines.next() else {
+                continue;
+            };
+            let parse_line = |line: Option<&str>, what: &str| -> Result<Vec<f64>, String> {
+                return line
+                    .ok_or_else(|| format!("missing {what}"))?
+                    .split_whitespace()
+                    .map(|v| v.parse::<f64>())
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| format!("invalid {what}"));
+            };
+            let x = parse_line(Some(x_line), "x knots")?;
+            let q = parse_line(lines.next(), "Q knots")?;
+            let pids = lines
+                .next()
+                .ok_or("missing flavours")?
+                .split_whitespace()
+                .map(|p| match p.parse::<i64>() {
+                    Ok(0) => Ok(21),
+                    pid => pid,
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| "invalid flavours")?;
+            if x.len() < 2 || q.is_empty() || pids.is_empty() {
+                return Err("empty subgrid".to_owned());
+            }
+            if x.iter().chain(q.iter()).any(|v| *v <= 0. || v.is_nan()) {
+                return Err("non-positive knot".to_owned());
+            }
+            let mut xf = Vec::with_capacity(x.len() * q.len() * pids.len());
+            for _ in 0..x.len() * q.len() {
+                let values = parse_line(lines.next(), "grid values")?;
+                if values.len() != pids.len() {
+                    return Err("wrong number of grid values".to_owned());
+                }
+                xf.extend(values);
+            }
+            subgrids.push(Subgrid {
+                ln_x: x.iter().map(|x| x.ln()).collect(),
+                ln_q2: q.iter().map(|q| (q * q).ln()).collect(),
+                pids,
+                xf,
+            });
+        }
+        if subgrids.is_empty() {
+            return Err("no subgrids".to_owned());
+        }
+        return Ok(Pdf { subgrids });
+    }
+
+    /// Momentum density `x f(x, Q^2)` of parton `pid` (`21` or `0` for the gluon)
+    pub(crate) fn xfx(&self, pid: i64, x: f64, q2: f64) -> f64 {
+        let pid = if pid == 0 { 21 } else { pid };
+        let ln_q2 = q2.ln();
+        let grid = self
+            .subgrids
+            .iter()
+            .find(|g| ln_q2 <= g.ln_q2[g.ln_q2.len() - 1])
+            .unwrap_or(&self.subgrids[self.subgrids.len() - 1]);
+        return match grid.pids.iter().position(|p| *p == pid) {
+            Some(ipid) => grid.xfx(ipid, x.ln(), ln_q2),
+            None => 0.,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdf_test() {
+        // x f(x, Q) = (1 + ln x) (2 + ln Q^2) for the gluon and its double for the up quark, which is interpolated
+        // exactly by the bilinear interpolation
+        let f = |x: f64, q: f64| (1. + x.ln()) * (2. + (q * q).ln());
+        let x = [1E-4, 1E-3, 1E-2, 0.1, 1.];
+        let mut content = String::from("PdfType: central\nFormat: lhagrid1\n---\n");
+        for q in [[1., 5.], [5., 100.]] {
+            content += &format!(
+                "{}\n{} {}\n0 2\n",
+                x.map(|x| x.to_string()).join(" "),
+                q[0],
+                q[1]
+            );
+            for x in x {
+                for q in q {
+                    content += &format!("{:e} {:e}\n", f(x, q), 2. * f(x, q));
+                }
+            }
+            content += "---\n";
+        }
+        let pdf = Pdf::parse(&content).unwrap();
+        assert_eq!(pdf.subgrids.len(), 2);
+        for (x, q) in [(3E-4, 2.), (0.5, 50.), (1E-2, 5.), (0.05, 99.)] {
+            assert!((pdf.xfx(21, x, q * q) / f(x, q) - 1.).abs() < 1E-12);
+            assert!((pdf.xfx(0, x, q * q) / f(x, q) - 1.).abs() < 1E-12);
+            assert!((pdf.xfx(2, x, q * q) / (2. * f(x, q)) - 1.).abs() < 1E-12);
+        }
+        assert_eq!(pdf.xfx(1, 0.1, 100.), 0.);
+        assert_eq!(pdf.xfx(21, 1E-6, 100.), pdf.xfx(21, 1E-4, 100.));
+        assert_eq!(pdf.xfx(21, 0.1, 1E6), pdf.xfx(21, 0.1, 1E4));
+
+        assert!(Pdf::parse("Format: lhagrid2\n---\n").is_err());
+        assert!(Pdf::parse("Format: lhagrid1\n---\n0.1 1\n1 2\n21\n1\n---\n").is_err());
+    }
+}
@@ -7,13 +7,52 @@ pub enum Scale<F: Float> {
     Reciprocal { min: F, max: F },
 }
 
+/// Source of random numbers reading from a point in the unit hypercube
+pub(crate) struct Hypercube<'a, F> {
+    u: &'a [F],
+    i: usize,
+}
+
+impl<'a, F> Hypercube<'a, F> {
+    pub(crate) fn new(u: &'a [F]) -> Self {
+        Hypercube { u, i: 0 }
+    }
+}
+
+impl<F: Float> FloatRandom<F> for Hypercube<'_, F> {
+    #[inline]
+    fn generate(&mut self) -> F {
+        self.i += 1;
+        return self.u[self.i - 1];
+    }
+
+    #[inline]
+    fn range(&mut self, min: F, max: F) -> F {
+        return min + self.generate() * (max - min);
+    }
+}
+
+/// Number of random numbers consumed by `rambo` for the given scale and number of outgoing particles
+pub(crate) fn dimension<F: Float>(s: &Scale<F>, n_out: usize) -> usize {
+    let n_scale = match s {
+        Scale::Fixed(_) => 0,
+        Scale::Uniform { .. } | Scale::Reciprocal { .. } => 1,
+    };
+    return n_scale + if n_out > 1 { 4 * n_out } else { 0 };
+}
+
 /// Generic RAMBO phase-space generator (10.1016/0010-4655(86)90119-0) based on the Fortran implementation of GoSam.
+///
+/// Returns the sampled center of mass energy squared, the momenta and the phase-space weight of the point, normalized
+/// to the phase-space measure `dPhi_n = prod_i d^3p_i / ((2 pi)^3 2 E_i) (2 pi)^4 delta^4(P - sum_i p_i)`. For
+/// `n_in = 1` the decaying particle is at rest with invariant mass `sqrt(s)`. For a single outgoing particle the
+/// weight is `2 pi`, i.e. the phase space `dPhi_1 = 2 pi delta(s - m^2)` without the delta function.
 pub(crate) fn rambo<F: Float + FloatConst>(
     s: Scale<F>,
     masses: &[F],
     n_in: usize,
     rng: &mut impl FloatRandom<F>,
-) -> (F, Vec<[F; 4]>) {
+) -> (F, Vec<[F; 4]>, F) {
     let s = match s {
         Scale::Fixed(s) => s,
         Scale::Uniform { min, max } => rng.range(min, max),
@@ -34,9 +73,10 @@ pub(crate) fn rambo<F: Float + FloatConst>(
         vecs[1][0] = b;
         vecs[1][3] = -(b * b - m2_sq).sqrt();
     } else {
-        vecs[0][0] = masses[0];
+        vecs[0][0] = s.sqrt();
     }
     let n = masses.len() - n_in;
+    let mut weight;
     if n > 1 {
         let mut q = Vec::with_capacity(n);
         let mut u: [F; 4];
@@ -77,23 +117,48 @@ pub(crate) fn rambo<F: Float + FloatConst>(
             vecs[m][2] = x * (b[1] * c + q[i][2]);
             vecs[m][3] = x * (b[2] * c + q[i][3]);
         }
-        let x = newton(s, &vecs[n_in..], &masses[n_in..]);
-        for i in n_in..vecs.len() {
-            vecs[i][0] = (masses[i] * masses[i] + x * x * vecs[i][0] * vecs[i][0]).sqrt();
-            vecs[i][1] = x * vecs[i][1];
-            vecs[i][2] = x * vecs[i][2];
-            vecs[i][3] = x * vecs[i][3];
+        weight = massless_weight(s, n);
+        if masses[n_in..].iter().any(|m| !m.is_zero()) {
+            let x = newton(s, &vecs[n_in..], &masses[n_in..]);
+            let mut prod = F::one();
+            let mut sum = F::zero();
+            for i in n_in..vecs.len() {
+                let p_abs = x * vecs[i][0];
+                vecs[i][0] = (masses[i] * masses[i] + p_abs * p_abs).sqrt();
+                vecs[i][1] = x * vecs[i][1];
+                vecs[i][2] = x * vecs[i][2];
+                vecs[i][3] = x * vecs[i][3];
+                prod = prod * p_abs / vecs[i][0];
+                sum = sum + p_abs * p_abs / vecs[i][0];
+            }
+            weight = weight * x.powi(2 * n as i32 - 3) * prod / sum * s.sqrt();
         }
     } else {
-        for i in 0..3 {
-            vecs[masses.len()][i] = vecs[0..vecs.len() - 1]
+        // A single outgoing particle carries the total incoming momentum, the remaining delta(s - m^2) is left to
+        // the caller
+        for i in 0..=3 {
+            vecs[n_in][i] = vecs[..n_in]
                 .iter()
                 .map(|v| v[i])
                 .fold(F::zero(), |a, b| a + b);
         }
+        weight = two * F::PI();
     }
 
-    return (s, vecs);
+    return (s, vecs, weight);
+}
+
+/// Phase-space volume of `n` massless particles with center of mass energy squared `s`
+fn massless_weight<F: Float + FloatConst>(s: F, n: usize) -> F {
+    let two: F = F::one() + F::one();
+    let mut weight = (F::FRAC_PI_2().ln() * F::from(n - 1).unwrap()
+        + s.ln() * F::from(n - 2).unwrap()
+        + (two * F::PI()).ln() * (F::from(4).unwrap() - F::from(3 * n).unwrap()))
+    .exp();
+    for k in 2..n {
+        weight = weight / (F::from(k).unwrap() * F::from(k - 1).unwrap());
+    }
+    return weight;
 }
 
 #[inline]
@@ -126,14 +191,16 @@ fn newton<F: Float>(s: F, vecs: &[[F; 4]], masses: &[F]) -> F {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::util::substream;
     use fastrand::Rng;
+    use std::f64::consts::PI;
     const N_ITER_TEST: usize = 1_000_000;
     #[test]
     fn rambo_test() {
         let mut rng = Rng::new();
         for n in 0..N_ITER_TEST {
             let masses = vec![0.0, 0.0, 125.0, 125.0, 0.0, 0.0];
-            let (_, vecs) = rambo(Scale::Fixed(500.0_f64.powi(2)), &masses, 2, &mut rng);
+            let (_, vecs, _) = rambo(Scale::Fixed(500.0_f64.powi(2)), &masses, 2, &mut rng);
             let e_ref = 0.5 * vecs.iter().map(|p| p[0].abs()).sum::<f64>();
             for i in 1..=3 {
                 let prec = vecs.iter().map(|p| p[i]).sum::<f64>().abs() / e_ref;
@@ -144,4 +211,96 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn rambo_seed_test() {
+        let masses = vec![0.0, 0.0, 125.0, 125.0, 0.0];
+        let s = || Scale::Uniform {
+            min: 300.0_f64.powi(2),
+            max: 1000.0_f64.powi(2),
+        };
+        let mut rng_a = substream(42, 3);
+        let mut rng_b = substream(42, 3);
+        let mut rng_c = substream(42, 4);
+        for _ in 0..1000 {
+            let (s_a, vecs_a, _) = rambo(s(), &masses, 2, &mut rng_a);
+            let (s_b, vecs_b, _) = rambo(s(), &masses, 2, &mut rng_b);
+            let (s_c, vecs_c, _) = rambo(s(), &masses, 2, &mut rng_c);
+            assert_eq!(s_a, s_b);
+            assert_eq!(vecs_a, vecs_b);
+            assert_ne!(s_a, s_c);
+            assert_ne!(vecs_a, vecs_c);
+        }
+    }
+
+    #[test]
+    fn rambo_hypercube_test() {
+        let masses = vec![0.0, 0.0, 125.0, 125.0, 0.0];
+        let s = || Scale::Reciprocal {
+            min: 300.0_f64.powi(2),
+            max: 1000.0_f64.powi(2),
+        };
+        let mut rng = Rng::with_seed(1);
+        let u = (0..dimension(&s(), 3))
+            .map(|_| rng.f64())
+            .collect::<Vec<_>>();
+        let (s_a, vecs_a, w_a) = rambo(s(), &masses, 2, &mut Rng::with_seed(1));
+        let (s_b, vecs_b, w_b) = rambo(s(), &masses, 2, &mut Hypercube::new(&u));
+        assert_eq!(s_a, s_b);
+        assert_eq!(vecs_a, vecs_b);
+        assert_eq!(w_a, w_b);
+    }
+
+    #[test]
+    fn rambo_weight_test() {
+        let mut rng = Rng::new();
+        let s = 500.0_f64.powi(2);
+        let (_, _, w) = rambo(Scale::Fixed(s), &[0., 0., 0., 0.], 2, &mut rng);
+        assert!((w * 8. * PI - 1.).abs() < 1E-12);
+        let (_, _, w) = rambo(Scale::Fixed(s), &[0., 0., 0., 0., 0.], 2, &mut rng);
+        assert!((w * 256. * PI.powi(3) / s - 1.).abs() < 1E-12);
+        let m = 173.;
+        let beta = (1. - 4. * m * m / s).sqrt();
+        let (_, _, w) = rambo(Scale::Fixed(s), &[0., 0., m, m], 2, &mut rng);
+        assert!((w * 8. * PI / beta - 1.).abs() < 1E-12);
+    }
+
+    #[test]
+    fn rambo_decay_test() {
+        let conserved = |vecs: &[[f64; 4]], n_in: usize| {
+            (0..=3).all(|i| {
+                let p_in = vecs[..n_in].iter().map(|p| p[i]).sum::<f64>();
+                let p_out = vecs[n_in..].iter().map(|p| p[i]).sum::<f64>();
+                (p_in - p_out).abs() < 1E-9 * vecs[0][0].abs().max(1.)
+            })
+        };
+        let mut rng = Rng::with_seed(1);
+        let m_h = 125.;
+        for _ in 0..10_000 {
+            let masses = [m_h, 4.8, 4.8, 0.];
+            let (s, vecs, w) = rambo(Scale::Fixed(m_h * m_h), &masses, 1, &mut rng);
+            assert_eq!(s, m_h * m_h);
+            assert_eq!(vecs[0], [m_h, 0., 0., 0.]);
+            assert!(conserved(&vecs, 1));
+            assert!(w > 0.);
+            for (p, m) in vecs.iter().zip(masses.iter()) {
+                let p_sq = p[0] * p[0] - p[1] * p[1] - p[2] * p[2] - p[3] * p[3];
+                assert!((p_sq - m * m).abs() < 1E-6 * m_h * m_h);
+            }
+        }
+        let m_b = 4.8;
+        let beta = (1. - 4. * m_b * m_b / (m_h * m_h)).sqrt();
+        let (_, vecs, w) = rambo(Scale::Fixed(m_h * m_h), &[m_h, m_b, m_b], 1, &mut rng);
+        assert!(conserved(&vecs, 1));
+        assert!((w * 8. * PI / beta - 1.).abs() < 1E-12);
+
+        let (_, vecs, w) = rambo(Scale::Fixed(m_h * m_h), &[0., 0., m_h], 2, &mut rng);
+        assert!(conserved(&vecs, 2));
+        assert_eq!(vecs[2], [m_h, 0., 0., 0.]);
+        assert_eq!(w, 2. * PI);
+        let (_, vecs, w) = rambo(Scale::Fixed(m_h * m_h), &[m_h, m_h], 1, &mut rng);
+        assert_eq!(vecs[0], vecs[1]);
+        assert_eq!(w, 2. * PI);
+        assert_eq!(dimension(&Scale::Fixed(m_h * m_h), 1), 0);
+    }
 }
@@ -0,0 +1,269 @@
+use indexmap::IndexMap;
+use pyo3::prelude::*;
+
+use crate::{
+    blha::{AmplitudeType, OLP, error::BLHAError},
+    kinematics::rotate,
+    result::{EvalResult, Status},
+};
+
+/// Step of the rescue system, applied in order to points whose accuracy is worse than the threshold until the point
+/// is stable
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Rescue {
+    /// Re-evaluate the point rotated by `angle` around `axis`, which changes the numerical path through the reduction
+    /// while leaving the Lorentz-invariant result unchanged
+    #[pyo3(constructor = (axis = [1., 1., 1.], angle = 1.))]
+    Rotate { axis: [f64; 3], angle: f64 },
+    /// Re-evaluate the point after setting the OLP parameters `switch`, e.g. to select a higher precision or an
+    /// alternative reduction, and set the parameters `restore` afterwards
+    #[pyo3(constructor = (switch, restore))]
+    Parameters {
+        switch: IndexMap<String, f64>,
+        restore: IndexMap<String, f64>,
+    },
+}
+
+impl Rescue {
+    /// Re-evaluate the subprocess with OLP id `id` at the point `momenta`, or `None` if the step does not apply to
+    /// `amplitude_type`.
+    /// Spin-correlated amplitudes depend on the frame and are not rotated. The caller has to hold the evaluation
+    /// lock of `olp`, such that switched parameters are not seen by other evaluations.
+    pub(crate) fn eval(
+        &self,
+        olp: &dyn OLP,
+        id: i64,
+        amplitude_type: AmplitudeType,
+        momenta: &[[f64; 4]],
+        scale: f64,
+    ) -> Option<Result<EvalResult, BLHAError>> {
+        return match self {
+            Rescue::Rotate { .. }
+                if matches!(
+                    amplitude_type,
+                    AmplitudeType::scTree | AmplitudeType::scTree2
+                ) =>
+            {
+                None
+            }
+            Rescue::Rotate { axis, angle } => {
+                let rotated = momenta
+                    .iter()
+                    .map(|p| rotate(p, axis, *angle))
+                    .collect::<Vec<_>>();
+                Some(olp.eval(id, &rotated, scale))
+            }
+            Rescue::Parameters { switch, restore } => Some(eval_with_parameters(
+                olp, switch, restore, id, momenta, scale,
+            )),
+        };
+    }
+}
+
+/// Evaluate with the parameters `switch` set and set the parameters `restore` afterwards, also if switching or the
+/// evaluation fails. Returns the first error.
+fn eval_with_parameters(
+    olp: &dyn OLP,
+    switch: &IndexMap<String, f64>,
+    restore: &IndexMap<String, f64>,
+    id: i64,
+    momenta: &[[f64; 4]],
+    scale: f64,
+) -> Result<EvalResult, BLHAError> {
+    let res = switch
+        .iter()
+        .try_for_each(|(parameter, value)| olp.set_parameter(parameter, *value, 0.))
+        .and_then(|_| olp.eval(id, momenta, scale));
+    // Restore all parameters even if switching or the evaluation failed, keeping the first error
+    let restored = restore
+        .iter()
+        .map(|(parameter, value)| olp.set_parameter(parameter, *value, 0.))
+        .fold(Ok(()), Result::and);
+    return res.and_then(|res| restored.map(|_| res));
+}
+
+/// Apply the rescue steps `steps` with `eval` to the result `res` if it is unstable with respect to `threshold`,
+/// until a step yields a stable result. If no step succeeds, the most accurate result is kept. Results of an unknown
+/// accuracy cannot be checked, so they are not rescued and rescue steps yielding them are skipped.
+pub(crate) fn rescue<E>(
+    steps: &[Rescue],
+    threshold: Option<f64>,
+    mut res: EvalResult,
+    mut eval: impl FnMut(&Rescue) -> Option<Result<EvalResult, E>>,
+) -> Result<EvalResult, E> {
+    if !res.unstable() {
+        return Ok(res);
+    }
+    for step in steps.iter() {
+        let Some(rescued) = eval(step) else {
+            continue;
+        };
+        let mut rescued = rescued?;
+        if rescued.accuracy.is_nan() {
+            continue;
+        }
+        rescued.check_accuracy(threshold);
+        if !rescued.unstable() {
+            rescued.status = Status::Rescued;
+            return Ok(rescued);
+        }
+        if rescued.accuracy < res.accuracy {
+            res = rescued;
+        }
+    }
+    return Ok(res);
+}
+
+#[pymethods]
+impl Rescue {
+    fn __repr__(&self) -> String {
+        return match self {
+            Rescue::Rotate { axis, angle } => {
+                format!("Rescue.Rotate(axis={axis:?}, angle={angle})")
+            }
+            Rescue::Parameters { switch, restore } => {
+                format!("Rescue.Parameters(switch={switch:?}, restore={restore:?})")
+            }
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blha::Contract;
+    use std::{path::Path, sync::Mutex};
+
+    #[test]
+    fn rescue_test() {
+        let steps = [
+            Rescue::Rotate {
+                axis: [1., 1., 1.],
+                angle: 1.,
+            },
+            Rescue::Parameters {
+                switch: IndexMap::from([("PSP_rescue".to_owned(), 1.)]),
+                restore: IndexMap::from([("PSP_rescue".to_owned(), 0.)]),
+            },
+        ];
+        let threshold = Some(1E-4);
+        let mut unstable = EvalResult::new(vec![1.], 1E-2);
+        unstable.check_accuracy(threshold);
+        let fake = |accuracy: [f64; 2]| {
+            return move |step: &Rescue| -> Option<Result<EvalResult, ()>> {
+                let i = if let Rescue::Rotate { .. } = step {
+                    0
+                } else {
+                    1
+                };
+                return Some(Ok(EvalResult::new(vec![i as f64 + 2.], accuracy[i])));
+            };
+        };
+
+        let stable = EvalResult::new(vec![0.], 1E-6);
+        assert_eq!(
+            rescue(&steps, threshold, stable.clone(), fake([0., 0.])),
+            Ok(stable)
+        );
+
+        let res = rescue(&steps, threshold, unstable.clone(), fake([1E-3, 1E-5])).unwrap();
+        assert_eq!(res.status, Status::Rescued);
+        assert_eq!(res.values, [3.]);
+
+        let res = rescue(&steps, threshold, unstable.clone(), fake([1E-5, 1E-5])).unwrap();
+        assert_eq!(res.values, [2.]);
+
+        let res = rescue(&steps, threshold, unstable.clone(), fake([1E-3, 1E-1])).unwrap();
+        assert_eq!(res.status, Status::Unstable);
+        assert_eq!((res.values[0], res.accuracy), (2., 1E-3));
+
+        // A step of unknown accuracy does not count as a rescue
+        let res = rescue(&steps, threshold, unstable.clone(), fake([f64::NAN, 1E-5])).unwrap();
+        assert_eq!((res.values[0], res.status), (3., Status::Rescued));
+
+        let res = rescue(
+            &steps,
+            threshold,
+            unstable.clone(),
+            |_| -> Option<Result<_, ()>> { None },
+        );
+        assert_eq!(res, Ok(unstable));
+    }
+
+    /// OLP recording the parameters set, failing to set the parameter `fail`
+    struct RecordingOLP {
+        contract: Contract,
+        eval_lock: Mutex<()>,
+        parameters: Mutex<Vec<(String, f64)>>,
+        fail: &'static str,
+    }
+
+    impl OLP for RecordingOLP {
+        fn contract(&self) -> &Contract {
+            return &self.contract;
+        }
+
+        fn eval_lock(&self) -> &Mutex<()> {
+            return &self.eval_lock;
+        }
+
+        fn load_copy(&self, _dir: &Path, _index: usize) -> Result<Box<dyn OLP>, BLHAError> {
+            return Err(BLHAError::FunctionError("copies".to_owned()));
+        }
+
+        fn set_parameter(&self, parameter: &str, real: f64, _imag: f64) -> Result<(), BLHAError> {
+            self.parameters
+                .lock()
+                .unwrap()
+                .push((parameter.to_owned(), real));
+            if parameter == self.fail {
+                return Err(BLHAError::OLPError("OLP_SetParameter".to_owned(), 0));
+            }
+            return Ok(());
+        }
+
+        fn print_parameters(&self, _filename: &str) -> Result<(), BLHAError> {
+            return Ok(());
+        }
+
+        fn eval(
+            &self,
+            _id: i64,
+            _momenta: &[[f64; 4]],
+            _scale: f64,
+        ) -> Result<EvalResult, BLHAError> {
+            return Ok(EvalResult::new(vec![1.; 4], 0.));
+        }
+    }
+
+    #[test]
+    fn parameters_restore_test() {
+        let olp = |fail| RecordingOLP {
+            contract: "21 21 -> 25 | 1 1".parse().unwrap(),
+            eval_lock: Mutex::new(()),
+            parameters: Mutex::new(Vec::new()),
+            fail,
+        };
+        let switch = IndexMap::from([("a".to_owned(), 1.), ("b".to_owned(), 1.)]);
+        let restore = IndexMap::from([("a".to_owned(), 0.), ("b".to_owned(), 0.)]);
+        let momenta = [[1., 0., 0., 1.], [1., 0., 0., -1.], [2., 0., 0., 0.]];
+
+        let ok = olp("");
+        assert!(eval_with_parameters(&ok, &switch, &restore, 1, &momenta, 2.).is_ok());
+        assert_eq!(ok.parameters.lock().unwrap().len(), 4);
+
+        // The failing switch stops the evaluation, but all parameters are restored
+        let failing = olp("a");
+        let res = eval_with_parameters(&failing, &switch, &restore, 1, &momenta, 2.);
+        assert!(matches!(res, Err(BLHAError::OLPError(_, _))));
+        assert_eq!(
+            *failing.parameters.lock().unwrap(),
+            [
+                ("a".to_owned(), 1.),
+                ("a".to_owned(), 0.),
+                ("b".to_owned(), 0.)
+            ]
+        );
+    }
+}
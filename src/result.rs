@@ -0,0 +1,356 @@
+use num_complex::Complex64;
+use pyo3::{
+    exceptions::{PyIndexError, PyValueError},
+    prelude::*,
+};
+
+use crate::blha::AmplitudeType;
+
+/// Numerical stability of an evaluated point, ordered from the best to the worst
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[pyclass]
+pub(crate) enum Status {
+    /// Accuracy within the threshold
+    Stable,
+    /// Accuracy within the threshold after a rescue step
+    Rescued,
+    /// Accuracy worse than the threshold after all rescue steps
+    Unstable,
+}
+
+/// Raw result of a single OLP evaluation together with the accuracy reported by the OLP
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct EvalResult {
+    pub(crate) values: Vec<f64>,
+    pub(crate) accuracy: f64,
+    pub(crate) status: Status,
+}
+
+impl EvalResult {
+    pub(crate) fn new(values: Vec<f64>, accuracy: f64) -> Self {
+        EvalResult {
+            values,
+            accuracy,
+            status: Status::Stable,
+        }
+    }
+
+    /// Flag the result as unstable if the reported accuracy is worse than `threshold`. An unknown accuracy (NaN)
+    /// cannot be checked and leaves the status unchanged.
+    pub(crate) fn check_accuracy(&mut self, threshold: Option<f64>) {
+        if let Some(threshold) = threshold
+            && !self.accuracy.is_nan()
+        {
+            self.status = if self.accuracy <= threshold {
+                Status::Stable
+            } else {
+                Status::Unstable
+            };
+        }
+    }
+
+    pub(crate) fn unstable(&self) -> bool {
+        return self.status == Status::Unstable;
+    }
+
+    /// Sum of the results of the channels of a subprocess, with the worst accuracy and status of the channels. The
+    /// accuracy is unknown if that of any channel is.
+    pub(crate) fn sum(channels: Vec<EvalResult>) -> Self {
+        let mut channels = channels.into_iter();
+        let mut sum = channels
+            .next()
+            .expect("Subprocesses have at least one channel");
+        for channel in channels {
+            sum.values
+                .iter_mut()
+                .zip(channel.values.iter())
+                .for_each(|(a, b)| *a += b);
+            sum.accuracy = if sum.accuracy.is_nan() || channel.accuracy.is_nan() {
+                f64::NAN
+            } else {
+                sum.accuracy.max(channel.accuracy)
+            };
+            sum.status = sum.status.max(channel.status);
+        }
+        return sum;
+    }
+
+    /// Interpret the raw values according to the BLHA2 layout of `amplitude_type` for a process with `n_legs` legs
+    pub(crate) fn typed(self, amplitude_type: AmplitudeType, n_legs: usize) -> TypedResult {
+        match amplitude_type {
+            AmplitudeType::Tree | AmplitudeType::Loop | AmplitudeType::LoopInduced => {
+                TypedResult::Loop(LoopResult {
+                    double_pole: self.values[0],
+                    single_pole: self.values[1],
+                    finite: self.values[2],
+                    born: self.values[3],
+                    accuracy: self.accuracy,
+                    status: self.status,
+                })
+            }
+            AmplitudeType::ccTree => TypedResult::ColorCorrelated(ColorCorrelatedResult {
+                values: self.values,
+                n_legs,
+                accuracy: self.accuracy,
+                status: self.status,
+            }),
+            AmplitudeType::scTree | AmplitudeType::scTree2 => {
+                TypedResult::SpinCorrelated(SpinCorrelatedResult {
+                    values: self.values,
+                    n_legs,
+                    accuracy: self.accuracy,
+                    status: self.status,
+                })
+            }
+        }
+    }
+}
+
+#[derive(IntoPyObject)]
+pub(crate) enum TypedResult {
+    Loop(LoopResult),
+    ColorCorrelated(ColorCorrelatedResult),
+    SpinCorrelated(SpinCorrelatedResult),
+}
+
+fn check_leg(i: usize, n_legs: usize) -> PyResult<()> {
+    if i >= n_legs {
+        return Err(PyIndexError::new_err(format!(
+            "Leg {i} out of range for process with {n_legs} legs"
+        )));
+    }
+    return Ok(());
+}
+
+fn py_status(status: Status) -> String {
+    return format!("Status.{status:?}");
+}
+
+/// Result of a `Tree`, `Loop` or `LoopInduced` amplitude
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct LoopResult {
+    #[pyo3(get)]
+    pub(crate) double_pole: f64,
+    #[pyo3(get)]
+    pub(crate) single_pole: f64,
+    #[pyo3(get)]
+    pub(crate) finite: f64,
+    #[pyo3(get)]
+    pub(crate) born: f64,
+    #[pyo3(get)]
+    pub(crate) accuracy: f64,
+    #[pyo3(get)]
+    pub(crate) status: Status,
+}
+
+#[pymethods]
+impl LoopResult {
+    #[getter]
+    fn values(&self) -> [f64; 4] {
+        return [self.double_pole, self.single_pole, self.finite, self.born];
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "LoopResult(double_pole={:e}, single_pole={:e}, finite={:e}, born={:e}, accuracy={:e}, status={})",
+            self.double_pole,
+            self.single_pole,
+            self.finite,
+            self.born,
+            self.accuracy,
+            py_status(self.status)
+        );
+    }
+}
+
+/// Result of a `ccTree` amplitude, i.e. the colour-correlated Born `<M|T_i T_j|M>` for all pairs of legs `i != j`
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ColorCorrelatedResult {
+    #[pyo3(get)]
+    pub(crate) values: Vec<f64>,
+    #[pyo3(get)]
+    pub(crate) n_legs: usize,
+    #[pyo3(get)]
+    pub(crate) accuracy: f64,
+    #[pyo3(get)]
+    pub(crate) status: Status,
+}
+
+impl ColorCorrelatedResult {
+    /// Colour correlator of legs `i` and `j`, stored at position `i + j(j-1)/2` for `i < j` as in BLHA2
+    pub(crate) fn get(&self, i: usize, j: usize) -> f64 {
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        return self.values[i + j * (j - 1) / 2];
+    }
+}
+
+#[pymethods]
+impl ColorCorrelatedResult {
+    fn __getitem__(&self, index: (usize, usize)) -> PyResult<f64> {
+        let (i, j) = index;
+        check_leg(i, self.n_legs)?;
+        check_leg(j, self.n_legs)?;
+        if i == j {
+            return Err(PyIndexError::new_err(
+                "Diagonal colour correlators are not provided by the OLP",
+            ));
+        }
+        return Ok(self.get(i, j));
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "ColorCorrelatedResult(n_legs={}, values={:?}, accuracy={:e}, status={})",
+            self.n_legs,
+            self.values,
+            self.accuracy,
+            py_status(self.status)
+        );
+    }
+}
+
+/// Result of a `scTree` or `scTree2` amplitude, i.e. the complex spin correlator
+/// `<M_{-,i}|T_i T_j|M_{+,i}>` of the helicity-flipped gluon `i` and the spectator `j`
+#[pyclass]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SpinCorrelatedResult {
+    #[pyo3(get)]
+    pub(crate) values: Vec<f64>,
+    #[pyo3(get)]
+    pub(crate) n_legs: usize,
+    #[pyo3(get)]
+    pub(crate) accuracy: f64,
+    #[pyo3(get)]
+    pub(crate) status: Status,
+}
+
+impl SpinCorrelatedResult {
+    /// Spin correlator of legs `i` and `j`, stored as real and imaginary part at position `2(i n + j)`
+    pub(crate) fn get(&self, i: usize, j: usize) -> Complex64 {
+        let k = 2 * (i * self.n_legs + j);
+        return Complex64::new(self.values[k], self.values[k + 1]);
+    }
+
+    /// Spin correlators `<M_{-h,i}|T_i T_j|M_{h,i}>` of leg `i` with helicity `h = ±1` in the ket and all spectators
+    /// `j`. BLHA2 provides `h = +1`, the correlators with `h = -1` are their complex conjugates since `T_i T_j` is
+    /// hermitian.
+    pub(crate) fn correlators(&self, i: usize, helicity: i32) -> Vec<Complex64> {
+        return (0..self.n_legs)
+            .map(|j| {
+                let c = self.get(i, j);
+                if helicity < 0 { c.conj() } else { c }
+            })
+            .collect();
+    }
+}
+
+#[pymethods]
+impl SpinCorrelatedResult {
+    fn __getitem__(&self, index: (usize, usize)) -> PyResult<Complex64> {
+        let (i, j) = index;
+        check_leg(i, self.n_legs)?;
+        check_leg(j, self.n_legs)?;
+        return Ok(self.get(i, j));
+    }
+
+    /// Spin correlators of the gluon `leg` with helicity `helicity` in the ket, indexed by the spectator
+    #[pyo3(name = "correlators")]
+    fn py_correlators(&self, leg: usize, helicity: i32) -> PyResult<Vec<Complex64>> {
+        check_leg(leg, self.n_legs)?;
+        if helicity.abs() != 1 {
+            return Err(PyValueError::new_err(format!(
+                "Helicity must be 1 or -1, got {helicity}"
+            )));
+        }
+        return Ok(self.correlators(leg, helicity));
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "SpinCorrelatedResult(n_legs={}, values={:?}, accuracy={:e}, status={})",
+            self.n_legs,
+            self.values,
+            self.accuracy,
+            py_status(self.status)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cc_layout_test() {
+        let n = 5;
+        let res = EvalResult::new((0..n * (n - 1) / 2).map(|i| i as f64).collect(), 0.)
+            .typed(AmplitudeType::ccTree, n);
+        let TypedResult::ColorCorrelated(cc) = res else {
+            panic!("Expected colour-correlated result");
+        };
+        let mut k = 0;
+        for j in 1..n {
+            for i in 0..j {
+                assert_eq!(cc.get(i, j), k as f64);
+                assert_eq!(cc.get(j, i), k as f64);
+                k += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn sc_layout_test() {
+        let n = 3;
+        let res = EvalResult::new((0..2 * n * n).map(|i| i as f64).collect(), 0.)
+            .typed(AmplitudeType::scTree, n);
+        let TypedResult::SpinCorrelated(sc) = res else {
+            panic!("Expected spin-correlated result");
+        };
+        assert_eq!(sc.get(1, 2), Complex64::new(10., 11.));
+        let plus = sc.correlators(1, 1);
+        let minus = sc.correlators(1, -1);
+        assert_eq!(plus.len(), n);
+        assert_eq!(plus[2], sc.get(1, 2));
+        assert_eq!(minus[2], Complex64::new(10., -11.));
+    }
+
+    #[test]
+    fn accuracy_test() {
+        let mut res = EvalResult::new(vec![0.; 4], 1E-3);
+        res.check_accuracy(None);
+        assert!(!res.unstable());
+        res.check_accuracy(Some(1E-4));
+        assert!(res.unstable());
+        assert_eq!(res.status, Status::Unstable);
+        res.check_accuracy(Some(1E-2));
+        assert!(!res.unstable());
+        assert_eq!(res.status, Status::Stable);
+
+        let mut unknown = EvalResult::new(vec![0.; 4], f64::NAN);
+        unknown.check_accuracy(Some(1E-4));
+        assert_eq!(unknown.status, Status::Stable);
+        unknown.status = Status::Unstable;
+        unknown.check_accuracy(Some(1E-4));
+        assert_eq!(unknown.status, Status::Unstable);
+    }
+
+    #[test]
+    fn channel_sum_test() {
+        let single = EvalResult::new(vec![1., 2.], 1E-6);
+        assert_eq!(EvalResult::sum(vec![single.clone()]), single);
+        let mut rescued = EvalResult::new(vec![3., -1.], 1E-8);
+        rescued.status = Status::Rescued;
+        let sum = EvalResult::sum(vec![single, rescued]);
+        assert_eq!(sum.values, [4., 1.]);
+        assert_eq!((sum.accuracy, sum.status), (1E-6, Status::Rescued));
+        let unknown = EvalResult::new(vec![0., 0.], f64::NAN);
+        assert!(
+            EvalResult::sum(vec![sum.clone(), unknown.clone()])
+                .accuracy
+                .is_nan()
+        );
+        assert!(EvalResult::sum(vec![unknown, sum]).accuracy.is_nan());
+    }
+}
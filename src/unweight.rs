@@ -0,0 +1,159 @@
+use fastrand::Rng;
+use pyo3::prelude::*;
+
+/// Hit-or-miss unweighting against a maximum weight fixed after the warm-up
+#[derive(Debug, Clone)]
+pub(crate) struct Unweighter {
+    max_weight: f64,
+    safety_factor: f64,
+    n_trials: usize,
+    n_accepted: usize,
+    n_overweight: usize,
+    rng: Rng,
+}
+
+impl Unweighter {
+    pub(crate) fn new(max_weight: f64, safety_factor: f64, rng: Rng) -> Self {
+        Unweighter {
+            max_weight,
+            safety_factor,
+            n_trials: 0,
+            n_accepted: 0,
+            n_overweight: 0,
+            rng,
+        }
+    }
+
+    /// Accept or reject a point with weight `w`. Accepted points get the unit event weight with the sign of `w`,
+    /// unless `w` exceeds the threshold `safety_factor * max_weight`. Such overweight points are accepted with weight
+    /// `w / threshold`. The maximum is kept fixed, such that all event weights are relative to the same threshold.
+    pub(crate) fn trial(&mut self, w: f64) -> Option<f64> {
+        self.n_trials += 1;
+        let threshold = self.safety_factor * self.max_weight;
+        if w.abs() > threshold {
+            self.n_overweight += 1;
+            self.n_accepted += 1;
+            return Some(w / threshold);
+        }
+        if w.abs() > self.rng.f64() * threshold {
+            self.n_accepted += 1;
+            return Some(w.signum());
+        }
+        return None;
+    }
+
+    pub(crate) fn n_accepted(&self) -> usize {
+        return self.n_accepted;
+    }
+
+    /// Estimated number of further trials needed to accept `n_events` events in total, from the efficiency so far.
+    /// `None` if no trial has been accepted yet.
+    pub(crate) fn trials_needed(&self, n_events: usize) -> Option<usize> {
+        let remaining = n_events.saturating_sub(self.n_accepted);
+        if self.n_trials == 0 {
+            return Some(remaining);
+        }
+        if self.n_accepted == 0 {
+            return None;
+        }
+        return Some(
+            (remaining as f64 * self.n_trials as f64 / self.n_accepted as f64).ceil() as usize,
+        );
+    }
+}
+
+/// Unweighted events of a subprocess
+#[pyclass]
+#[derive(Debug, Clone)]
+pub(crate) struct UnweightedEvents {
+    #[pyo3(get)]
+    pub(crate) momenta: Vec<Vec<[f64; 4]>>,
+    #[pyo3(get)]
+    pub(crate) weights: Vec<f64>,
+    #[pyo3(get)]
+    pub(crate) max_weight: f64,
+    #[pyo3(get)]
+    pub(crate) n_trials: usize,
+    #[pyo3(get)]
+    pub(crate) n_overweight: usize,
+    #[pyo3(get)]
+    pub(crate) integral: f64,
+    #[pyo3(get)]
+    pub(crate) integral_error: f64,
+}
+
+impl UnweightedEvents {
+    pub(crate) fn new(
+        momenta: Vec<Vec<[f64; 4]>>,
+        weights: Vec<f64>,
+        unweighter: &Unweighter,
+        integral: f64,
+        integral_error: f64,
+    ) -> Self {
+        UnweightedEvents {
+            momenta,
+            weights,
+            max_weight: unweighter.max_weight,
+            n_trials: unweighter.n_trials,
+            n_overweight: unweighter.n_overweight,
+            integral,
+            integral_error,
+        }
+    }
+}
+
+#[pymethods]
+impl UnweightedEvents {
+    #[getter]
+    fn efficiency(&self) -> f64 {
+        return self.weights.len() as f64 / self.n_trials as f64;
+    }
+
+    fn __len__(&self) -> usize {
+        return self.weights.len();
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "UnweightedEvents(n_events={}, n_trials={}, efficiency={:e}, n_overweight={})",
+            self.weights.len(),
+            self.n_trials,
+            self.efficiency(),
+            self.n_overweight
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::substream;
+
+    #[test]
+    fn unweighting_test() {
+        let mut rng = substream(1, 0);
+        let mut unweighter = Unweighter::new(1., 1., substream(1, 1));
+        let mut n_low = 0;
+        while unweighter.n_accepted() < 100_000 {
+            let x = rng.f64();
+            if unweighter.trial(x).is_some() && x < 0.5 {
+                n_low += 1;
+            }
+        }
+        // For w(x) = x, a quarter of the events lie below x = 1/2 and half of the trials are accepted
+        assert!((n_low as f64 / 25_000. - 1.).abs() < 2E-2);
+        assert!((unweighter.n_trials as f64 / 200_000. - 1.).abs() < 1E-2);
+        assert_eq!(unweighter.n_overweight, 0);
+
+        // Another 100000 events need about as many trials as the first ones
+        let needed = unweighter.trials_needed(200_000).unwrap();
+        assert!((needed as f64 / 200_000. - 1.).abs() < 1E-2);
+
+        let mut unweighter = Unweighter::new(1., 1.5, Rng::with_seed(2));
+        assert_eq!(unweighter.trials_needed(10), Some(10));
+        assert_eq!(unweighter.trial(3.), Some(2.));
+        assert_eq!(unweighter.trial(-6.), Some(-4.));
+        assert_eq!(unweighter.n_overweight, 2);
+        assert_eq!(unweighter.max_weight, 1.);
+    }
+}
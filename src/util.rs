@@ -19,6 +19,19 @@ impl FloatRandom<f64> for Rng {
     }
 }
 
+#[inline]
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    return z ^ (z >> 31);
+}
+
+/// Independent random number stream `stream` derived from `seed`
+pub(crate) fn substream(seed: u64, stream: u64) -> Rng {
+    return Rng::with_seed(splitmix64(splitmix64(seed) ^ stream));
+}
+
 #[inline]
 pub(crate) fn scalar(p: &[f64; 4], q: &[f64; 4]) -> f64 {
     p[0] * q[0] - p[1] * q[1] - p[2] * q[2] - p[3] * q[3]
@@ -0,0 +1,330 @@
+use pyo3::prelude::*;
+
+use crate::{
+    kinematics::{boost, invariants, rotate},
+    util::FloatRandom,
+};
+
+/// Number of digits reported for identical values
+pub(crate) const MAX_DIGITS: f64 = 16.;
+/// Maximal velocity of the random boosts applied by `random_boost`
+const MAX_BETA: f64 = 0.9;
+
+/// Number of digits to which `a` and `b` agree
+pub(crate) fn digits(a: f64, b: f64) -> f64 {
+    if a == b {
+        return MAX_DIGITS;
+    }
+    let scale = a.abs().max(b.abs());
+    return (-((a - b).abs() / scale).log10()).clamp(0., MAX_DIGITS);
+}
+
+/// Smallest number of digits to which the entries of `a` and `b` agree
+pub(crate) fn agreement(a: &[f64], b: &[f64]) -> f64 {
+    return a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| digits(*a, *b))
+        .fold(MAX_DIGITS, f64::min);
+}
+
+/// Number of digits by which `value` is suppressed with respect to `reference`, used to check that the poles of a
+/// finite amplitude vanish
+pub(crate) fn suppression(value: f64, reference: f64) -> f64 {
+    if value == 0. {
+        return MAX_DIGITS;
+    }
+    return (-(value / reference).abs().log10()).clamp(0., MAX_DIGITS);
+}
+
+/// Uniformly distributed unit vector
+fn random_direction(rng: &mut impl FloatRandom<f64>) -> [f64; 3] {
+    let cos_theta = 2. * rng.generate() - 1.;
+    let sin_theta = (1. - cos_theta * cos_theta).sqrt();
+    let (sin_phi, cos_phi) = (2. * std::f64::consts::PI * rng.generate()).sin_cos();
+    return [sin_theta * cos_phi, sin_theta * sin_phi, cos_theta];
+}
+
+/// Rotate all momenta of `vecs` by a random angle around a random axis
+pub(crate) fn random_rotation(vecs: &[[f64; 4]], rng: &mut impl FloatRandom<f64>) -> Vec<[f64; 4]> {
+    let axis = random_direction(rng);
+    let angle = 2. * std::f64::consts::PI * rng.generate();
+    return vecs.iter().map(|p| rotate(p, &axis, angle)).collect();
+}
+
+/// Boost all momenta of `vecs` in a random direction with a velocity below `MAX_BETA`
+pub(crate) fn random_boost(vecs: &[[f64; 4]], rng: &mut impl FloatRandom<f64>) -> Vec<[f64; 4]> {
+    let beta = MAX_BETA * rng.generate();
+    let beta = random_direction(rng).map(|n| beta * n);
+    return vecs.iter().map(|p| boost(p, &beta)).collect();
+}
+
+/// Massless coloured leg entering the infrared structure of a one-loop QCD amplitude
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct ColouredLeg {
+    pub(crate) index: usize,
+    /// Quadratic Casimir `T_i^2` of the colour representation
+    pub(crate) casimir: f64,
+    /// Collinear anomalous dimension, `3/2 C_F` for quarks and `11/6 C_A - 2/3 T_R n_f` for gluons
+    pub(crate) gamma: f64,
+}
+
+impl ColouredLeg {
+    /// Leg `index` with colour representation `color` (`3`, `-3` or `8`) and `n_f` light flavours, `None` for other
+    /// representations
+    pub(crate) fn new(index: usize, color: i64, n_f: usize) -> Option<Self> {
+        const C_F: f64 = 4. / 3.;
+        const C_A: f64 = 3.;
+        return match color {
+            3 | -3 => Some(ColouredLeg {
+                index,
+                casimir: C_F,
+                gamma: 1.5 * C_F,
+            }),
+            8 => Some(ColouredLeg {
+                index,
+                casimir: C_A,
+                gamma: 11. / 6. * C_A - n_f as f64 / 3.,
+            }),
+            _ => None,
+        };
+    }
+}
+
+/// Colour correlators `<T_i T_j> / <1>` of the legs `i` and `j` of `legs` fixed by colour conservation, which is only
+/// possible for at most three coloured legs
+pub(crate) fn casimir_correlator(legs: &[ColouredLeg], i: usize, j: usize) -> Option<f64> {
+    return match legs {
+        [a, _] => Some(-a.casimir),
+        [_, _, _] => {
+            let k = (0..3).find(|k| *k != i && *k != j)?;
+            Some(0.5 * (legs[k].casimir - legs[i].casimir - legs[j].casimir))
+        }
+        _ => None,
+    };
+}
+
+/// Expected double and single pole of the renormalized one-loop amplitude in units of `alpha_s / (2 pi)` in the
+/// normalization `(4 pi)^eps / Gamma(1 - eps)` (hep-ph/9605323), given the Born `born` and the colour correlators
+/// `correlator(i, j) = <T_i T_j>` of the entries `i` and `j` of the massless coloured legs `legs`
+pub(crate) fn ir_poles(
+    vecs: &[[f64; 4]],
+    n_in: usize,
+    legs: &[ColouredLeg],
+    born: f64,
+    correlator: impl Fn(usize, usize) -> f64,
+    mu: f64,
+) -> (f64, f64) {
+    let n = vecs.len();
+    let s = invariants(vecs, n_in);
+    let double_pole = -legs.iter().map(|leg| leg.casimir).sum::<f64>() * born;
+    let mut single_pole = -legs.iter().map(|leg| leg.gamma).sum::<f64>() * born;
+    for (i, a) in legs.iter().enumerate() {
+        for (j, b) in legs.iter().enumerate().skip(i + 1) {
+            let s_ij = s[a.index * n + b.index].abs();
+            single_pole += 2. * correlator(i, j) * (mu * mu / s_ij).ln();
+        }
+    }
+    return (double_pole, single_pole);
+}
+
+/// Digits of agreement of the self-checks of `Process.validate`, the minimum over all points and values
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ValidationResult {
+    #[pyo3(get)]
+    pub(crate) n_points: usize,
+    /// Agreement after a random rotation of each point
+    #[pyo3(get)]
+    pub(crate) rotation: f64,
+    /// Agreement after a random boost of each point
+    #[pyo3(get)]
+    pub(crate) boost: f64,
+    /// Agreement with the subprocess with exchanged incoming particles, evaluated at exchanged incoming momenta,
+    /// `None` if there is no such subprocess
+    #[pyo3(get)]
+    pub(crate) crossing: Option<f64>,
+    /// Agreement of the double pole with the expected infrared structure, `None` if not checked
+    #[pyo3(get)]
+    pub(crate) double_pole: Option<f64>,
+    /// Agreement of the single pole with the expected infrared structure, `None` if not checked
+    #[pyo3(get)]
+    pub(crate) single_pole: Option<f64>,
+}
+
+impl ValidationResult {
+    pub(crate) fn new(
+        check_crossing: bool,
+        check_double_pole: bool,
+        check_single_pole: bool,
+    ) -> Self {
+        return ValidationResult {
+            n_points: 0,
+            rotation: MAX_DIGITS,
+            boost: MAX_DIGITS,
+            crossing: check_crossing.then_some(MAX_DIGITS),
+            double_pole: check_double_pole.then_some(MAX_DIGITS),
+            single_pole: check_single_pole.then_some(MAX_DIGITS),
+        };
+    }
+}
+
+#[pymethods]
+impl ValidationResult {
+    /// Smallest number of digits of all checks
+    #[getter]
+    fn digits(&self) -> f64 {
+        return [self.crossing, self.double_pole, self.single_pole]
+            .into_iter()
+            .flatten()
+            .fold(self.rotation.min(self.boost), f64::min);
+    }
+
+    fn __repr__(&self) -> String {
+        let fmt = |d: Option<f64>| d.map_or("None".to_owned(), |d| format!("{d:.1}"));
+        return format!(
+            "ValidationResult(n_points={}, rotation={:.1}, boost={:.1}, crossing={}, double_pole={}, single_pole={})",
+            self.n_points,
+            self.rotation,
+            self.boost,
+            fmt(self.crossing),
+            fmt(self.double_pole),
+            fmt(self.single_pole)
+        );
+    }
+}
+
+/// Poles of a `Loop` amplitude at a single point together with the prediction of the I-operator
+#[pyclass]
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PoleCheck {
+    #[pyo3(get)]
+    pub(crate) double_pole: f64,
+    #[pyo3(get)]
+    pub(crate) single_pole: f64,
+    #[pyo3(get)]
+    pub(crate) expected_double_pole: f64,
+    #[pyo3(get)]
+    pub(crate) expected_single_pole: f64,
+}
+
+#[pymethods]
+impl PoleCheck {
+    /// Digits of agreement of the double pole with the prediction
+    #[getter]
+    fn double_pole_digits(&self) -> f64 {
+        return digits(self.double_pole, self.expected_double_pole);
+    }
+
+    /// Digits of agreement of the single pole with the prediction
+    #[getter]
+    fn single_pole_digits(&self) -> f64 {
+        return digits(self.single_pole, self.expected_single_pole);
+    }
+
+    fn __repr__(&self) -> String {
+        return format!(
+            "PoleCheck(double_pole={:e}, expected_double_pole={:e}, single_pole={:e}, expected_single_pole={:e})",
+            self.double_pole,
+            self.expected_double_pole,
+            self.single_pole,
+            self.expected_single_pole
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kinematics::{abs_p, mass};
+    use fastrand::Rng;
+
+    #[test]
+    fn digits_test() {
+        assert_eq!(digits(1., 1.), MAX_DIGITS);
+        assert!((digits(1., 1. + 1E-8) - 8.).abs() < 1E-6);
+        assert_eq!(digits(1., -1.), 0.);
+        assert_eq!(agreement(&[1., 2.], &[1., 2.0002]), digits(2., 2.0002));
+        assert!((suppression(1E-10, -10.) - 11.).abs() < 1E-12);
+
+        let mut rng = Rng::with_seed(1);
+        let vecs = [[10., 1., 2., 3.], [20., -4., 5., 6.]];
+        for transformed in [
+            random_rotation(&vecs, &mut rng),
+            random_boost(&vecs, &mut rng),
+        ] {
+            assert!((invariants(&transformed, 1)[1] - invariants(&vecs, 1)[1]).abs() < 1E-10);
+            assert!((mass(&transformed[0]) - mass(&vecs[0])).abs() < 1E-10);
+            assert!((transformed[0][1] - vecs[0][1]).abs() > 1E-6);
+        }
+        let rotated = random_rotation(&vecs, &mut rng);
+        assert_eq!(rotated[1][0], vecs[1][0]);
+        assert!((abs_p(&rotated[1]) - abs_p(&vecs[1])).abs() < 1E-12);
+    }
+
+    #[test]
+    fn ir_poles_test() {
+        // gamma* -> q q~ with V = alpha_s / (2 pi) C_F (-2 / eps^2 - (3 + 2 ln(mu^2 / s)) / eps + ...) * born
+        let vecs = [
+            [100., 0., 0., 0.],
+            [50., 30., 0., 40.],
+            [50., -30., 0., -40.],
+        ];
+        let legs = [
+            ColouredLeg::new(1, 3, 5).unwrap(),
+            ColouredLeg::new(2, -3, 5).unwrap(),
+        ];
+        let c_f = 4. / 3.;
+        let born = 2.5;
+        let mu: f64 = 30.;
+        let (double_pole, single_pole) = ir_poles(
+            &vecs,
+            1,
+            &legs,
+            born,
+            |i, j| casimir_correlator(&legs, i, j).unwrap() * born,
+            mu,
+        );
+        assert!((double_pole + 2. * c_f * born).abs() < 1E-12);
+        let expected = -c_f * (3. + 2. * (mu * mu / 1E4).ln()) * born;
+        assert!((single_pole - expected).abs() < 1E-12);
+
+        // Colour conservation sum_{j != i} T_i T_j = -T_i^2 for q q~ g
+        let legs = [
+            ColouredLeg::new(0, 3, 5).unwrap(),
+            ColouredLeg::new(1, -3, 5).unwrap(),
+            ColouredLeg::new(2, 8, 5).unwrap(),
+        ];
+        for i in 0..3 {
+            let sum = (0..3)
+                .filter(|j| *j != i)
+                .map(|j| casimir_correlator(&legs, i, j).unwrap())
+                .sum::<f64>();
+            assert!((sum + legs[i].casimir).abs() < 1E-12);
+        }
+        assert!((legs[2].gamma - (5.5 - 5. / 3.)).abs() < 1E-12);
+        assert_eq!(casimir_correlator(&[legs[0]; 4], 0, 1), None);
+
+        // q q~ -> q' q~' with colour correlators obeying colour conservation, for which the single pole changes by
+        // the double pole under mu^2 -> e mu^2
+        let vecs = [
+            [50., 0., 0., 50.],
+            [50., 0., 0., -50.],
+            [50., 30., 0., 40.],
+            [50., -30., 0., -40.],
+        ];
+        let legs = (0..4)
+            .map(|i| ColouredLeg::new(i, 3, 5).unwrap())
+            .collect::<Vec<_>>();
+        let correlator = |i: usize, j: usize| {
+            return match i + j {
+                1 | 5 => -1.,
+                2 | 4 => -0.5,
+                _ => 1. / 6.,
+            } * born;
+        };
+        let (double_pole, low) = ir_poles(&vecs, 2, &legs, born, correlator, mu);
+        let (_, high) = ir_poles(&vecs, 2, &legs, born, correlator, mu * 0.5_f64.exp());
+        assert!((high - low - double_pole).abs() < 1E-12);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{io::prelude::*, path::Path};
+
+/// Adaptive VEGAS grid (10.1016/0021-9991(78)90004-9) mapping the unit hypercube onto itself
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VegasGrid {
+    edges: Vec<Vec<f64>>,
+}
+
+impl VegasGrid {
+    /// Uniform grid with `n_bins` bins in each of the `dim` dimensions
+    pub(crate) fn new(dim: usize, n_bins: usize) -> Self {
+        let edges = (0..=n_bins).map(|i| i as f64 / n_bins as f64).collect();
+        VegasGrid {
+            edges: vec![edges; dim],
+        }
+    }
+
+    pub(crate) fn dim(&self) -> usize {
+        return self.edges.len();
+    }
+
+    pub(crate) fn n_bins(&self) -> usize {
+        return self.edges[0].len() - 1;
+    }
+
+    /// Map the uniformly distributed point `u` to `x` and return the Jacobian of the mapping
+    pub(crate) fn map(&self, u: &[f64], x: &mut [f64]) -> f64 {
+        let n_bins = self.n_bins();
+        let mut jacobian = 1.;
+        for (d, edges) in self.edges.iter().enumerate() {
+            let y = u[d] * n_bins as f64;
+            let i = (y as usize).min(n_bins - 1);
+            let width = edges[i + 1] - edges[i];
+            x[d] = edges[i] + (y - i as f64) * width;
+            jacobian *= n_bins as f64 * width;
+        }
+        return jacobian;
+    }
+
+    /// Adapt the grid to the accumulated integrand, where `alpha` controls the rate of convergence
+    pub(crate) fn refine(&mut self, acc: &VegasAccumulator, alpha: f64) {
+        for (edges, d) in self.edges.iter_mut().zip(acc.d.iter()) {
+            refine_dim(edges, d, alpha);
+        }
+    }
+
+    pub(crate) fn save(&self, path: &Path) -> Result<(), std::io::Error> {
+        let mut writer = std::io::BufWriter::new(std::fs::File::create(path)?);
+        writeln!(writer, "# VEGAS grid generated by pygs")?;
+        writeln!(writer, "{} {}", self.dim(), self.n_bins())?;
+        for edges in self.edges.iter() {
+            writeln!(
+                writer,
+                "{}",
+                edges
+                    .iter()
+                    .map(|e| format!("{e:e}"))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn load(path: &Path) -> Result<Self, std::io::Error> {
+        let invalid = |msg: &str| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {msg}", path.to_str().unwrap()),
+            )
+        };
+        let content = std::fs::read_to_string(path)?;
+        let mut lines = content.lines().filter(|l| !l.starts_with('#'));
+        let header = lines
+            .next()
+            .ok_or_else(|| invalid("missing header"))?
+            .split_whitespace()
+            .map(|n| n.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| invalid("invalid header"))?;
+        let [dim, n_bins] = header[..] else {
+            return Err(invalid("invalid header"));
+        };
+        let mut edges = Vec::with_capacity(dim);
+        for line in lines.take(dim) {
+            let e = line
+                .split_whitespace()
+                .map(|e| e.parse::<f64>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid("invalid bin edge"))?;
+            if e.len() != n_bins + 1 {
+                return Err(invalid("wrong number of bin edges"));
+            }
+            edges.push(e);
+        }
+        if edges.len() != dim || n_bins == 0 {
+            return Err(invalid("wrong number of dimensions"));
+        }
+        return Ok(VegasGrid { edges });
+    }
+}
+
+fn refine_dim(edges: &mut [f64], d: &[f64], alpha: f64) {
+    let n = d.len();
+    let smoothed = (0..n)
+        .map(|i| {
+            let (lo, hi) = (i.saturating_sub(1), (i + 1).min(n - 1));
+            d[lo..=hi].iter().sum::<f64>() / (hi - lo + 1) as f64
+        })
+        .collect::<Vec<_>>();
+    let total = smoothed.iter().sum::<f64>();
+    if total <= 0. || total.is_nan() {
+        return;
+    }
+    let w = smoothed
+        .iter()
+        .map(|d| {
+            let r = d / total;
+            if r <= 0. {
+                0.
+            } else if r >= 1. {
+                1.
+            } else {
+                ((r - 1.) / r.ln()).powf(alpha)
+            }
+        })
+        .collect::<Vec<_>>();
+    let per_bin = w.iter().sum::<f64>() / n as f64;
+    let old = edges.to_vec();
+    let mut acc = 0.;
+    let mut j = 0;
+    for (k, edge) in edges.iter_mut().enumerate().take(n).skip(1) {
+        let target = k as f64 * per_bin;
+        while j < n - 1 && acc + w[j] < target {
+            acc += w[j];
+            j += 1;
+        }
+        let frac = if w[j] > 0. {
+            ((target - acc) / w[j]).clamp(0., 1.)
+        } else {
+            0.
+        };
+        *edge = old[j] + frac * (old[j + 1] - old[j]);
+    }
+}
+
+/// Accumulated squared integrand per bin of a `VegasGrid`
+#[derive(Debug, Clone)]
+pub(crate) struct VegasAccumulator {
+    d: Vec<Vec<f64>>,
+}
+
+impl VegasAccumulator {
+    pub(crate) fn new(grid: &VegasGrid) -> Self {
+        VegasAccumulator {
+            d: vec![vec![0.; grid.n_bins()]; grid.dim()],
+        }
+    }
+
+    /// Add the integrand `f` (including the Jacobian of the grid) at the uniformly distributed point `u`
+    pub(crate) fn add(&mut self, u: &[f64], f: f64) {
+        for (d, u) in self.d.iter_mut().zip(u.iter()) {
+            let i = ((u * d.len() as f64) as usize).min(d.len() - 1);
+            d[i] += f * f;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrate::Accumulator;
+    use fastrand::Rng;
+
+    #[test]
+    fn vegas_test() {
+        let f = |x: &[f64]| (-100. * ((x[0] - 0.3).powi(2) + (x[1] - 0.6).powi(2))).exp();
+        let mut rng = Rng::with_seed(1);
+        let mut grid = VegasGrid::new(2, 50);
+        let mut x = [0.; 2];
+        let mut errors = Vec::new();
+        for _ in 0..10 {
+            let mut acc = Accumulator::default();
+            let mut vegas_acc = VegasAccumulator::new(&grid);
+            for _ in 0..10_000 {
+                let u = [rng.f64(), rng.f64()];
+                let value = grid.map(&u, &mut x) * f(&x);
+                acc.add(value);
+                vegas_acc.add(&u, value);
+            }
+            assert!(
+                (acc.mean() * 100. / std::f64::consts::PI - 1.).abs() < 5. * acc.error() * 100.
+            );
+            errors.push(acc.error());
+            grid.refine(&vegas_acc, 1.5);
+        }
+        assert!(errors.last().unwrap() * 5. < errors[0]);
+
+        let path =
+            std::env::temp_dir().join(format!("pygs_vegas_test_{}.grid", std::process::id()));
+        grid.save(&path).unwrap();
+        let loaded = VegasGrid::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(grid, loaded);
+    }
+}